@@ -0,0 +1,238 @@
+// Version taken form https://github.com/AlexanderReaper7/aoc-auto/blob/master/src/lib.rs
+//! Generates the auto_import.rs and mod.rs files that register every year and day.
+//!
+//! A day either has `part1` and `part2` functions or implements `crate::solution::Solution`.
+//! Public functions named after a part with a suffix, like `part2_fast`, are registered as
+//! variants of that part.
+
+use proc_macro2::Span;
+use quote::quote;
+use std::{fs, path::Path};
+
+/// Run this function from the build script to generate the auto_import.rs and mod.rs files for
+/// each year folder and day file.
+///
+/// Years and days are sorted so the output doesn't depend on the order of the file system, and
+/// files are only rewritten when their content changes so they don't trigger another build.
+///
+/// # Example
+///
+/// ```no_run
+/// use aoc_auto::aoc_auto;
+/// fn main() {
+///    aoc_auto();
+/// }
+/// ```
+pub fn aoc_auto() {
+    // every folder in src/ formatted as y20XX/ is a year
+    let years = numbered_entries(Path::new("src/"), "y", "");
+    for year in &years {
+        let year_dir = Path::new("src/").join(format!("y{}", year));
+        println!("cargo::rerun-if-changed={}", year_dir.display());
+        // every file in the year folder formatted as dX.rs is a day
+        let days = numbered_entries(&year_dir, "d", ".rs");
+        let days_mod: Vec<syn::Ident> =
+            days.iter().map(|day| ident(&format!("d{}", day))).collect();
+        let days_num: Vec<syn::LitInt> = days.iter().map(|day| lit(*day)).collect();
+        let solutions: Vec<Option<syn::Type>> = days
+            .iter()
+            .map(|day| solution_type(&year_dir.join(format!("d{}.rs", day))))
+            .collect();
+        let day_solutions =
+            days_mod
+                .iter()
+                .zip(&solutions)
+                .map(|(day_mod, solution)| match solution {
+                    Some(ty) => quote! { Box::new(crate::solution::Typed::<#day_mod::#ty>::new()) },
+                    None => quote! {
+                        Box::new(crate::solution::Functions {
+                            part1: #day_mod::part1,
+                            part2: #day_mod::part2,
+                        })
+                    },
+                });
+        let variants: Vec<Vec<(u8, String)>> = days
+            .iter()
+            .map(|day| variant_names(&year_dir.join(format!("d{}.rs", day))))
+            .collect();
+        let variant_lists = variants.iter().map(|day_variants| {
+            let names = day_variants.iter().map(|(_, name)| name);
+            quote! { &[#(#names),*] }
+        });
+        let mut variant_arms = vec![];
+        for (((day_mod, day_num), solution), day_variants) in days_mod
+            .iter()
+            .zip(&days_num)
+            .zip(&solutions)
+            .zip(&variants)
+        {
+            for (part, name) in day_variants {
+                let func = ident(name);
+                let variant = match (solution, part) {
+                    (Some(ty), _) => quote! {
+                        crate::solution::Variant::<#day_mod::#ty>::new(#part, #day_mod::#func)
+                    },
+                    (None, 1) => quote! {
+                        crate::solution::Functions { part1: #day_mod::#func, part2: #day_mod::part2 }
+                    },
+                    (None, _) => quote! {
+                        crate::solution::Functions { part1: #day_mod::part1, part2: #day_mod::#func }
+                    },
+                };
+                variant_arms.push(quote! { (#day_num, #name) => Ok(Box::new(#variant)), });
+            }
+        }
+        let mod_code = quote! {
+            //! Auto-generated file by build script, do not edit!
+            #(pub mod #days_mod;)*
+
+            /// Selects the solution for the given day
+            pub fn select_day(day: u32) -> Result<Box<dyn crate::solution::DynSolution>, String> {
+                match day {
+                    #(#days_num => Ok(#day_solutions),)*
+                    _ => Err("Invalid day!".into()),
+                }
+            }
+
+            /// Returns the names of the variants of the given day
+            pub fn variants(day: u32) -> &'static [&'static str] {
+                match day {
+                    #(#days_num => #variant_lists,)*
+                    _ => &[],
+                }
+            }
+
+            /// Selects the given day with one part replaced by the named variant
+            #[allow(clippy::match_single_binding)]
+            pub fn select_variant(day: u32, name: &str) -> Result<Box<dyn crate::solution::DynSolution>, String> {
+                match (day, name) {
+                    #(#variant_arms)*
+                    _ => Err("Invalid variant!".into()),
+                }
+            }
+        };
+        write_and_format(mod_code.to_string(), &year_dir.join("mod.rs"));
+    }
+
+    let years_mod: Vec<syn::Ident> = years
+        .iter()
+        .map(|year| ident(&format!("y{}", year)))
+        .collect();
+    let years_path: Vec<String> = years
+        .iter()
+        .map(|year| format!("y{}/mod.rs", year))
+        .collect();
+    let years_num: Vec<syn::LitInt> = years.iter().map(|year| lit(*year)).collect();
+    let auto_import_code = quote! {
+        //! Auto-generated file by build script, do not edit!
+        #(
+            #[path = #years_path]
+            pub mod #years_mod;
+        )*
+        /// Selects the solution for the given year and day
+        pub fn select_day(year: u32, day: u32) -> Result<Box<dyn crate::solution::DynSolution>, String> {
+            match year {
+                #(#years_num => Ok(#years_mod::select_day(day)?),)*
+                _ => Err("Invalid year!".into()),
+            }
+        }
+        /// Returns the names of the variants of the given year and day
+        pub fn variants(year: u32, day: u32) -> &'static [&'static str] {
+            match year {
+                #(#years_num => #years_mod::variants(day),)*
+                _ => &[],
+            }
+        }
+        /// Selects the given year and day with one part replaced by the named variant
+        pub fn select_variant(year: u32, day: u32, name: &str) -> Result<Box<dyn crate::solution::DynSolution>, String> {
+            match year {
+                #(#years_num => Ok(#years_mod::select_variant(day, name)?),)*
+                _ => Err("Invalid year!".into()),
+            }
+        }
+    };
+    write_and_format(
+        auto_import_code.to_string(),
+        Path::new("src/auto_import.rs"),
+    );
+}
+
+/// Returns the sorted numbers of the entries named `{prefix}{number}{suffix}` in `dir`
+fn numbered_entries(dir: &Path, prefix: &str, suffix: &str) -> Vec<u32> {
+    let mut numbers: Vec<u32> = dir
+        .read_dir()
+        .unwrap()
+        .map(|e| e.unwrap())
+        .filter(|e| {
+            // folders have no suffix, files do
+            if suffix.is_empty() {
+                e.path().is_dir()
+            } else {
+                e.path().is_file()
+            }
+        })
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            let number = name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            // every character between the prefix and the suffix is a digit
+            if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            number.parse().ok()
+        })
+        .collect();
+    numbers.sort();
+    numbers
+}
+
+/// Returns the type a day implements `Solution` for, days without one use `part1`/`part2` functions
+fn solution_type(path: &Path) -> Option<syn::Type> {
+    let code = fs::read_to_string(path).unwrap();
+    let file = syn::parse_file(&code).unwrap();
+    file.items.into_iter().find_map(|item| match item {
+        syn::Item::Impl(item) => {
+            let (_, trait_path, _) = item.trait_.as_ref()?;
+            let name = &trait_path.segments.last()?.ident;
+            (name == "Solution").then_some(*item.self_ty)
+        }
+        _ => None,
+    })
+}
+
+/// Returns the part and name of every public `part1_*` and `part2_*` function of a day
+fn variant_names(path: &Path) -> Vec<(u8, String)> {
+    let code = fs::read_to_string(path).unwrap();
+    let file = syn::parse_file(&code).unwrap();
+    file.items
+        .into_iter()
+        .filter_map(|item| match item {
+            syn::Item::Fn(func) if matches!(func.vis, syn::Visibility::Public(_)) => {
+                let name = func.sig.ident.to_string();
+                let (part, suffix) = name.strip_prefix("part")?.split_once('_')?;
+                let part = match part {
+                    "1" => 1,
+                    "2" => 2,
+                    _ => return None,
+                };
+                (!suffix.is_empty()).then_some((part, name))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn ident(name: &str) -> syn::Ident {
+    syn::Ident::new(name, Span::call_site())
+}
+
+fn lit(number: u32) -> syn::LitInt {
+    syn::LitInt::new(&number.to_string(), Span::call_site())
+}
+
+fn write_and_format(code: String, path: &Path) {
+    let syntax_tree = syn::parse_file(&code).unwrap();
+    let text = prettyplease::unparse(&syntax_tree);
+    if fs::read_to_string(path).ok().as_deref() != Some(text.as_str()) {
+        fs::write(path, text).unwrap();
+    }
+}
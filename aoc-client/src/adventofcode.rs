@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::blocking::RequestBuilder;
+use reqwest::header::COOKIE;
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+use crate::calendar;
+use crate::provider::{PuzzleProvider, Submitted};
+use crate::session::user_name;
+use crate::submit::{cooldown, solved_parts};
+use crate::{ClientError, SolutionPart, SubmitOutcome, Throttle};
+
+type Params<'a> = HashMap<&'a str, String>;
+
+const BASE_URL: &str = "https://adventofcode.com";
+/// Tells Advent of Code who is sending the requests, as it asks automated tools to
+pub const USER_AGENT: &str = concat!(
+    "advent/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/nikostoulas/advent)"
+);
+
+/// Puzzles of adventofcode.com, for the account of the session cookie
+pub struct AdventOfCode {
+    session_token: String,
+    client: reqwest::blocking::Client,
+    base_url: String,
+    throttle: Option<Throttle>,
+}
+
+impl AdventOfCode {
+    pub fn new(session_token: impl Into<String>) -> Self {
+        Self {
+            session_token: session_token.into(),
+            client: reqwest::blocking::Client::builder()
+                .user_agent(USER_AGENT)
+                .build()
+                .expect("the HTTP client can't be set up"),
+            base_url: BASE_URL.to_string(),
+            throttle: None,
+        }
+    }
+
+    /// Waits for the throttle before every request
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Talks to another server instead of adventofcode.com
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// The name of the user the session belongs to, from the settings page
+    pub fn user(&self) -> Result<String, ClientError> {
+        let url = format!("{}/settings", self.base_url);
+        let page = self.send(self.client.get(&url), &url, None)?;
+        user_name(&page).ok_or(ClientError::SessionExpired)
+    }
+
+    fn get_request(&self, url: &str, year: u32, day: u8) -> Result<String, ClientError> {
+        self.send(self.client.get(url), url, Some((year, day)))
+    }
+
+    fn post_request(
+        &self,
+        url: &str,
+        params: &Params,
+        year: u32,
+        day: u8,
+    ) -> Result<String, ClientError> {
+        self.send(self.client.post(url).form(&params), url, Some((year, day)))
+    }
+
+    /// Sends the request with the session cookie and tells the ways it can fail apart
+    ///
+    /// Advent of Code answers an invalid cookie with a 400 when asking for an input and with a
+    /// page asking to log in otherwise, and a puzzle that isn't out yet with a 404.
+    fn send(
+        &self,
+        request: RequestBuilder,
+        url: &str,
+        puzzle: Option<(u32, u8)>,
+    ) -> Result<String, ClientError> {
+        if let Some(throttle) = &self.throttle {
+            throttle.wait()?;
+        }
+        let cookie = format!("session={}", self.session_token);
+        let response = request.header(COOKIE, cookie).send()?;
+        match (response.status(), puzzle) {
+            (StatusCode::BAD_REQUEST, _) => return Err(ClientError::SessionExpired),
+            (StatusCode::NOT_FOUND, Some((year, day))) => {
+                return Err(ClientError::NotUnlocked { year, day })
+            }
+            (status, _) if !status.is_success() => {
+                return Err(ClientError::Status {
+                    url: url.to_string(),
+                    status: status.as_u16(),
+                })
+            }
+            _ => {}
+        }
+        let text = response.text()?;
+        if text.contains("href=\"/auth/login\"") {
+            return Err(ClientError::SessionExpired);
+        }
+        Ok(text)
+    }
+}
+
+impl PuzzleProvider for AdventOfCode {
+    fn fetch_input(&self, year: u32, day: u8) -> Result<String, ClientError> {
+        check_unlocked(year, day)?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        self.get_request(&url, year, day)
+    }
+
+    /// Advent of Code answers the same for a part that was already solved and a part that can't
+    /// be submitted yet, so the page of the day is checked to tell them apart.
+    fn submit(&self, solution_part: &SolutionPart, answer: &str) -> Result<Submitted, ClientError> {
+        let SolutionPart { year, day, part } = *solution_part;
+        solution_part
+            .validate()
+            .map_err(ClientError::NoSuchPuzzle)?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let mut params = Params::new();
+        params.insert("level", part.to_string());
+        params.insert("answer", answer.to_string());
+
+        let response = self.post_request(&url, &params, year, day)?;
+        let mut outcome = SubmitOutcome::parse(&response);
+        if outcome == SubmitOutcome::WrongLevel {
+            let url = format!("{}/{}/day/{}", self.base_url, year, day);
+            if let Ok(page) = self.get_request(&url, year, day) {
+                if solved_parts(&page) >= part as usize {
+                    outcome = SubmitOutcome::AlreadySolved;
+                }
+            }
+        }
+        Ok(Submitted {
+            outcome,
+            cooldown: cooldown(&response),
+        })
+    }
+
+    fn fetch_description(&self, year: u32, day: u8) -> Result<String, ClientError> {
+        check_unlocked(year, day)?;
+        let url = format!("{}/{}/day/{}", self.base_url, year, day);
+        self.get_request(&url, year, day)
+    }
+
+    fn days(&self, year: u32) -> Result<Vec<u8>, ClientError> {
+        let days = calendar::days_in_year(year)
+            .ok_or_else(|| ClientError::NoSuchPuzzle(format!("there was no event in {}", year)))?;
+        Ok((1..=days).collect())
+    }
+
+    /// Every puzzle unlocks at midnight US Eastern time, see [`calendar`]
+    fn time_until_unlock(&self, year: u32, day: u8) -> Option<Duration> {
+        calendar::time_until_unlock(year, day, OffsetDateTime::now_utc())
+    }
+
+    /// The start of the SHA-256 of the session cookie, enough to tell accounts apart
+    fn account(&self) -> String {
+        let hash = format!("{:x}", Sha256::digest(self.session_token.as_bytes()));
+        format!("session {}", &hash[..12])
+    }
+}
+
+/// Fails for a puzzle that doesn't exist or isn't out yet, without asking the server
+fn check_unlocked(year: u32, day: u8) -> Result<(), ClientError> {
+    SolutionPart::create(year, day, 1)
+        .validate()
+        .map_err(ClientError::NoSuchPuzzle)?;
+    match calendar::time_until_unlock(year, day, OffsetDateTime::now_utc()) {
+        Some(_) => Err(ClientError::NotUnlocked { year, day }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::StubServer;
+
+    #[test]
+    fn test_error_responses() {
+        let server = StubServer::start(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+                    .to_string(),
+            ),
+            (404, "404 Not Found".to_string()),
+            (
+                200,
+                "<html><a href=\"/auth/login\">[Log In]</a></html>".to_string(),
+            ),
+            (500, "".to_string()),
+        ]);
+        let provider = AdventOfCode::new("token").with_base_url(&server.url);
+        let errors: Vec<ClientError> = (0..4)
+            .map(|_| provider.fetch_input(2024, 12).unwrap_err())
+            .collect();
+        assert!(matches!(errors[0], ClientError::SessionExpired));
+        assert!(matches!(
+            errors[1],
+            ClientError::NotUnlocked {
+                year: 2024,
+                day: 12
+            }
+        ));
+        assert!(matches!(errors[2], ClientError::SessionExpired));
+        assert!(matches!(errors[3], ClientError::Status { status: 500, .. }));
+        assert_eq!(server.requests()[0].line, "GET /2024/day/12/input HTTP/1.1");
+    }
+
+    #[test]
+    fn test_user() {
+        let settings = "<html><header><div class=\"user\">(anonymous user #1234) \
+            <span class=\"star-count\">12*</span></div></header><main></main></html>";
+        let server = StubServer::start(vec![
+            (200, settings.to_string()),
+            (
+                200,
+                "<html><a href=\"/auth/login\">[Log In]</a></html>".to_string(),
+            ),
+        ]);
+        let provider = AdventOfCode::new("token").with_base_url(&server.url);
+        assert_eq!(provider.user().unwrap(), "(anonymous user #1234)");
+        assert!(matches!(provider.user(), Err(ClientError::SessionExpired)));
+        let request = &server.requests()[0];
+        assert_eq!(request.line, "GET /settings HTTP/1.1");
+        assert_eq!(request.cookie.as_deref(), Some("session=token"));
+    }
+
+    #[test]
+    fn test_future_puzzle_is_not_requested() {
+        let server = StubServer::start(vec![]);
+        let provider = AdventOfCode::new("token").with_base_url(&server.url);
+        let (year, day) = calendar::next_unlock(OffsetDateTime::now_utc());
+        let err = provider.fetch_input(year, day).unwrap_err();
+        assert!(matches!(err, ClientError::NotUnlocked { .. }));
+        let err = provider.fetch_description(2025, 13).unwrap_err();
+        assert!(matches!(err, ClientError::NoSuchPuzzle(_)));
+        assert_eq!(provider.days(2025).unwrap(), (1..=12).collect::<Vec<_>>());
+        assert!(provider.days(2014).is_err());
+        assert!(server.requests().is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::ClientError;
+
+/// The answers Advent of Code accepted for the days of a year, by day and part
+///
+/// Scraped from the day pages and recorded when a submission is correct. Stored as JSON in
+/// `answers/yYYYY.json` under the cache directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub year: u32,
+    path: PathBuf,
+    days: BTreeMap<u8, Vec<String>>,
+}
+
+impl Answers {
+    /// Loads the answers of a year, none if nothing was recorded yet
+    pub fn load(cache_dir: &Path, year: u32) -> Result<Self, ClientError> {
+        let path = cache_dir.join(format!("answers/y{}.json", year));
+        let days = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|err| {
+                ClientError::Cache(format!("could not read {}: {}", path.display(), err))
+            })?,
+            Err(_) => BTreeMap::new(),
+        };
+        Ok(Self { year, path, days })
+    }
+
+    /// The accepted answer of a part, if it is known
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        let answers = self.days.get(&day)?;
+        answers
+            .get(part as usize - 1)
+            .filter(|answer| !answer.is_empty())
+            .map(String::as_str)
+    }
+
+    /// Records the answers of a day, in part order, and saves the store
+    ///
+    /// Empty answers are unknown and keep the answer recorded before, if any.
+    pub fn record(&mut self, day: u8, answers: Vec<String>) -> Result<(), ClientError> {
+        let known = self.days.get(&day).cloned().unwrap_or_default();
+        let mut merged = known.clone();
+        merged.resize(known.len().max(answers.len()), String::new());
+        for (merged, answer) in merged.iter_mut().zip(answers) {
+            if !answer.is_empty() {
+                *merged = answer;
+            }
+        }
+        if merged == known {
+            return Ok(());
+        }
+        self.days.insert(day, merged);
+        self.save()
+    }
+
+    /// Records the answer of a single part, keeping the others
+    pub fn record_part(&mut self, day: u8, part: u8, answer: &str) -> Result<(), ClientError> {
+        let mut answers = vec![String::new(); part as usize];
+        answers[part as usize - 1] = answer.to_string();
+        self.record(day, answers)
+    }
+
+    fn save(&self) -> Result<(), ClientError> {
+        fs::create_dir_all(self.path.parent().unwrap())?;
+        let json = serde_json::to_string_pretty(&self.days)
+            .map_err(|err| ClientError::Cache(err.to_string()))?;
+        fs::write(&self.path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let cache_dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        let mut answers = Answers::load(&cache_dir, 2024).unwrap();
+        assert_eq!(answers.get(6, 1), None);
+        answers
+            .record(6, vec!["4515".to_string(), "1309".to_string()])
+            .unwrap();
+        answers.record_part(7, 2, "11387").unwrap();
+        // a page scraped before part 2 was solved
+        answers.record(7, vec!["3749".to_string()]).unwrap();
+        let loaded = Answers::load(&cache_dir, 2024).unwrap();
+        assert_eq!(loaded, answers);
+        assert_eq!(loaded.get(6, 2), Some("1309"));
+        assert_eq!(loaded.get(7, 1), Some("3749"));
+        assert_eq!(loaded.get(7, 2), Some("11387"));
+        assert_eq!(loaded.get(8, 1), None);
+        assert!(cache_dir.join("answers/y2024.json").is_file());
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
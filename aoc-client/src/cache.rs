@@ -0,0 +1,502 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{CacheKey, ClientError, Config};
+
+/// Bodies Advent of Code sends instead of an input, and what they mean
+const ERROR_BODIES: [(&str, &str); 4] = [
+    (
+        "Please don't repeatedly request this endpoint",
+        "a request to slow down",
+    ),
+    ("Puzzle inputs differ by user", "a request to log in"),
+    ("404 Not Found", "a missing page"),
+    ("Internal Server Error", "a server error"),
+];
+
+/// What is known about a cached input, kept beside it in `dD.meta.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// Seconds since the Unix epoch
+    pub downloaded_at: u64,
+    /// Size of the cached file, the encrypted one for encrypted inputs
+    pub length: usize,
+    /// Hex encoded SHA-256 of the cached file, so an encrypted input's doesn't leak the input
+    pub sha256: String,
+    /// The account the input was downloaded for, see [`PuzzleProvider::account`]
+    ///
+    /// Only plain inputs have to match the account reading them, encrypted ones are shared.
+    ///
+    /// [`PuzzleProvider::account`]: crate::PuzzleProvider::account
+    pub account: String,
+}
+
+/// A cached input and what is wrong with it, if anything
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u8,
+    pub path: PathBuf,
+    /// Without the key an encrypted input is only checked against its metadata
+    pub encrypted: bool,
+    /// Inputs cached before metadata was kept have none
+    pub metadata: Option<Metadata>,
+    pub problem: Option<String>,
+}
+
+/// The inputs under the cache directory, `yYYYY/dD.txt` with their metadata
+///
+/// With a [`CacheKey`] inputs are written encrypted to `dD.txt.enc` instead, so the inputs can
+/// be committed. Encrypted inputs are read transparently, plain ones keep working.
+///
+/// Only `yYYYY/dD.txt.enc` and the `dD.meta.json` beside it are safe to commit, the project's
+/// `.gitignore` keeps the rest of the cache out. The puzzle pages in `yYYYY/dD.html`, the
+/// examples, answers and submissions are written in plain text, they hold the puzzle text.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+    key: Option<CacheKey>,
+}
+
+impl InputCache {
+    pub fn new(cache_dir: &Path) -> Self {
+        Self {
+            dir: cache_dir.to_path_buf(),
+            key: None,
+        }
+    }
+
+    /// The cache of the configuration, encrypted if it has a `cache_key`
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.cache_dir).with_key(config.cache_key.clone())
+    }
+
+    pub fn with_key(mut self, key: Option<CacheKey>) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// The cached input of the day, if it is intact and was downloaded for the account
+    ///
+    /// Encrypted inputs are shared by everyone with the key, so they are read whichever account
+    /// they were downloaded for. Without an account, like when there is no session to tell it,
+    /// any account will do too.
+    pub fn read(&self, year: u32, day: u8, account: Option<&str>) -> Option<String> {
+        let input = self.load(year, day).ok()?;
+        let account = account.filter(|_| !self.is_encrypted(year, day));
+        match (read_metadata(&self.path(year, day)), account) {
+            (Some(metadata), Some(account)) if metadata.account != account => None,
+            _ => Some(input),
+        }
+    }
+
+    /// The cached input of the day for whichever account it was downloaded, if it is intact
+    pub fn load(&self, year: u32, day: u8) -> Result<String, ClientError> {
+        let path = self.path(year, day);
+        let (input, file) = match self.contents(year, day) {
+            Ok((Some(input), file)) => (input, file),
+            Ok((None, _)) => {
+                return Err(ClientError::Cache(format!(
+                    "{} is encrypted, set cache_key in advent.toml or ADVENT_CACHE_KEY to read it",
+                    encrypted_path(&path).display()
+                )))
+            }
+            Err(problem) => {
+                return Err(ClientError::Cache(format!(
+                    "{} {}",
+                    path.display(),
+                    problem
+                )))
+            }
+        };
+        match check(Some(&input), &file, read_metadata(&path).as_ref()) {
+            Some(problem) => Err(ClientError::Cache(format!(
+                "{} {}",
+                path.display(),
+                problem
+            ))),
+            None => Ok(input),
+        }
+    }
+
+    /// Caches the input with its metadata, refusing anything that isn't an input
+    ///
+    /// An encrypted input is never replaced by a plain one, nor by one encrypted with another
+    /// key, it may be committed and shared.
+    pub fn write(&self, year: u32, day: u8, input: &str, account: &str) -> Result<(), ClientError> {
+        if let Some(problem) = looks_like_error(input) {
+            return Err(ClientError::Cache(format!(
+                "the input of day {} of {} is {}, not caching it",
+                day, year, problem
+            )));
+        }
+        let path = self.path(year, day);
+        let encrypted = encrypted_path(&path);
+        if encrypted.exists() {
+            match &self.key {
+                None => {
+                    return Err(ClientError::Cache(format!(
+                        "{} is encrypted, set cache_key in advent.toml or ADVENT_CACHE_KEY \
+                         instead of caching the input in plain text beside it",
+                        encrypted.display()
+                    )))
+                }
+                Some(_) if !matches!(self.contents(year, day), Ok((Some(_), _))) => {
+                    return Err(ClientError::Cache(format!(
+                        "{} can't be decrypted with the cache key, remove it to cache the \
+                         input again",
+                        encrypted.display()
+                    )))
+                }
+                Some(_) => {}
+            }
+        }
+        fs::create_dir_all(path.parent().unwrap())?;
+        let file = match &self.key {
+            Some(key) => {
+                let blob = key.seal(&context(year, day), input);
+                fs::write(&encrypted, &blob)?;
+                // a plain copy would leak the input and could disagree with the encrypted one
+                if path.exists() {
+                    fs::remove_file(&path)?;
+                }
+                blob
+            }
+            None => {
+                fs::write(&path, input)?;
+                input.as_bytes().to_vec()
+            }
+        };
+        let metadata = Metadata {
+            downloaded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+            length: file.len(),
+            sha256: format!("{:x}", Sha256::digest(&file)),
+            account: account.to_string(),
+        };
+        let json = serde_json::to_string_pretty(&metadata)
+            .map_err(|err| ClientError::Cache(err.to_string()))?;
+        fs::write(metadata_path(&path), json)?;
+        Ok(())
+    }
+
+    /// Every cached input, of a single year if one is given, in order
+    pub fn entries(&self, year: Option<u32>) -> Result<Vec<Entry>, ClientError> {
+        let mut entries = vec![];
+        for (year, year_dir) in numbered(&self.dir, "y", "")? {
+            if !year_dir.is_dir() {
+                continue;
+            }
+            for day in cached_days(&year_dir, &[".txt", ".txt.enc"])? {
+                let day = day as u8;
+                let path = self.path(year, day);
+                let metadata = read_metadata(&path);
+                let problem = match self.contents(year, day) {
+                    Ok((input, file)) => check(input.as_deref(), &file, metadata.as_ref()),
+                    Err(problem) => Some(problem),
+                };
+                let encrypted = self.is_encrypted(year, day);
+                entries.push(Entry {
+                    year,
+                    day,
+                    path: if encrypted {
+                        encrypted_path(&path)
+                    } else {
+                        path
+                    },
+                    encrypted,
+                    metadata,
+                    problem,
+                });
+            }
+        }
+        entries.retain(|entry| year.is_none_or(|year| entry.year == year));
+        entries.sort_by_key(|entry| (entry.year, entry.day));
+        Ok(entries)
+    }
+
+    /// Removes the cached inputs and puzzle pages of a year, or of a single day of it
+    ///
+    /// Submissions, answers and examples are kept. Returns the number of days removed.
+    pub fn clear(&self, year: Option<u32>, day: Option<u8>) -> Result<usize, ClientError> {
+        let mut removed = 0;
+        for (entry_year, year_dir) in numbered(&self.dir, "y", "")? {
+            if year.is_some_and(|year| year != entry_year) {
+                continue;
+            }
+            let mut days = cached_days(&year_dir, &[".txt", ".txt.enc", ".html"])?;
+            days.retain(|entry_day| day.is_none_or(|day| day as u32 == *entry_day));
+            for entry_day in &days {
+                let path = year_dir.join(format!("d{}.txt", entry_day));
+                for path in [
+                    metadata_path(&path),
+                    encrypted_path(&path),
+                    path.with_extension("html"),
+                    path,
+                ] {
+                    if path.exists() {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+            removed += days.len();
+        }
+        Ok(removed)
+    }
+
+    fn path(&self, year: u32, day: u8) -> PathBuf {
+        self.dir.join(format!("y{}/d{}.txt", year, day))
+    }
+
+    /// Whether the file `contents` reads is the encrypted one
+    fn is_encrypted(&self, year: u32, day: u8) -> bool {
+        let path = self.path(year, day);
+        encrypted_path(&path).exists() && (self.key.is_some() || !path.exists())
+    }
+
+    /// The input as cached, `None` if it is encrypted and there is no key to read it, with the
+    /// bytes of the file it is cached in
+    fn contents(&self, year: u32, day: u8) -> Result<(Option<String>, Vec<u8>), String> {
+        let path = self.path(year, day);
+        let encrypted = encrypted_path(&path);
+        let read =
+            |path: &Path| fs::read(path).map_err(|err| format!("could not be read: {}", err));
+        match (&self.key, encrypted.exists(), path.exists()) {
+            (Some(key), true, _) => {
+                let blob = read(&encrypted)?;
+                Ok((Some(key.open(&context(year, day), &blob)?), blob))
+            }
+            (_, _, true) => {
+                let file = read(&path)?;
+                let input = String::from_utf8(file.clone()).map_err(|_| "is not text")?;
+                Ok((Some(input), file))
+            }
+            (None, true, false) => Ok((None, read(&encrypted)?)),
+            _ => Err("is not cached".to_string()),
+        }
+    }
+}
+
+/// What an encrypted input is bound to, so it can't be moved to another day
+fn context(year: u32, day: u8) -> String {
+    format!("y{}/d{}", year, day)
+}
+
+fn encrypted_path(path: &Path) -> PathBuf {
+    path.with_extension("txt.enc")
+}
+
+/// The days with a file ending with one of the suffixes in the directory of a year
+fn cached_days(year_dir: &Path, suffixes: &[&str]) -> Result<Vec<u32>, ClientError> {
+    let mut days: Vec<u32> = suffixes
+        .iter()
+        .map(|suffix| numbered(year_dir, "d", suffix))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .map(|(day, _)| day)
+        .collect();
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
+/// What is wrong with a cached input and the file it is cached in, if anything
+///
+/// An encrypted input that can't be decrypted is only checked against its metadata.
+fn check(input: Option<&str>, file: &[u8], metadata: Option<&Metadata>) -> Option<String> {
+    if let Some(problem) = input.and_then(looks_like_error) {
+        return Some(format!("is {}", problem));
+    }
+    let metadata = metadata?;
+    if metadata.length != file.len() {
+        return Some(format!(
+            "has {} bytes instead of {}",
+            file.len(),
+            metadata.length
+        ));
+    }
+    if format!("{:x}", Sha256::digest(file)) != metadata.sha256 {
+        return Some("doesn't match its SHA-256".to_string());
+    }
+    None
+}
+
+/// Tells what an input really is when it's a page or an error message
+fn looks_like_error(input: &str) -> Option<&'static str> {
+    let start = input.trim_start();
+    if start.is_empty() {
+        return Some("empty");
+    }
+    // inputs may start with `<` too, like the moves of 2015 day 3
+    let head = start.chars().take(9).collect::<String>().to_lowercase();
+    if head.starts_with("<!doctype") || head.starts_with("<html") {
+        return Some("an HTML page");
+    }
+    ERROR_BODIES
+        .iter()
+        .find(|(body, _)| input.contains(body))
+        .map(|(_, problem)| *problem)
+}
+
+fn metadata_path(path: &Path) -> PathBuf {
+    path.with_extension("meta.json")
+}
+
+fn read_metadata(path: &Path) -> Option<Metadata> {
+    let json = fs::read_to_string(metadata_path(path)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// The entries of a directory named like `y2024` or `d6.txt`, with their number
+fn numbered(dir: &Path, prefix: &str, suffix: &str) -> Result<Vec<(u32, PathBuf)>, ClientError> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(_) => return Ok(vec![]),
+    };
+    let mut found = vec![];
+    for entry in read_dir {
+        let path = entry?.path();
+        let number = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(prefix))
+            .and_then(|name| name.strip_suffix(suffix))
+            .and_then(|number| number.parse().ok());
+        if let Some(number) = number {
+            found.push((number, path));
+        }
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(name: &str) -> (InputCache, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        (InputCache::new(&dir), dir)
+    }
+
+    #[test]
+    fn test_looks_like_error() {
+        assert_eq!(looks_like_error("1 2 3\n"), None);
+        assert_eq!(
+            looks_like_error("<!DOCTYPE html>\n<html></html>"),
+            Some("an HTML page")
+        );
+        assert_eq!(
+            looks_like_error(
+                "Please don't repeatedly request this endpoint before it unlocks! The calendar \
+                 countdown is synchronized with the server time; the link will be enabled on \
+                 the calendar the instant this puzzle becomes available.\n"
+            ),
+            Some("a request to slow down")
+        );
+        assert_eq!(looks_like_error("\n"), Some("empty"));
+    }
+
+    #[test]
+    fn test_read_and_write() {
+        let (cache, dir) = cache("rw");
+        cache.write(2024, 6, "1 2 3\n", "alice").unwrap();
+        assert_eq!(
+            cache.read(2024, 6, Some("alice")).as_deref(),
+            Some("1 2 3\n")
+        );
+        // inputs differ by account
+        assert_eq!(cache.read(2024, 6, Some("bob")), None);
+        assert_eq!(cache.read(2024, 6, None).as_deref(), Some("1 2 3\n"));
+        let metadata = read_metadata(&dir.join("y2024/d6.txt")).unwrap();
+        assert_eq!(metadata.length, 6);
+        assert_eq!(metadata.account, "alice");
+        assert!(cache.write(2024, 7, "<html></html>", "alice").is_err());
+        assert!(!dir.join("y2024/d7.txt").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_encrypted() {
+        let (plain, dir) = cache("encrypted");
+        plain.write(2024, 5, "4 5 6\n", "alice").unwrap();
+        let cache = plain
+            .clone()
+            .with_key(Some(CacheKey::from_hex(&"5e".repeat(32)).unwrap()));
+        cache.write(2024, 6, "1 2 3\n", "alice").unwrap();
+        assert!(!dir.join("y2024/d6.txt").exists());
+        let blob = fs::read(dir.join("y2024/d6.txt.enc")).unwrap();
+        assert!(!blob.starts_with(b"1 2 3"));
+        // the metadata describes the encrypted file, not the input
+        let metadata = read_metadata(&dir.join("y2024/d6.txt")).unwrap();
+        assert_eq!(metadata.length, blob.len());
+        assert_eq!(metadata.sha256, format!("{:x}", Sha256::digest(&blob)));
+        assert_eq!(
+            cache.read(2024, 6, Some("alice")).as_deref(),
+            Some("1 2 3\n")
+        );
+        // encrypted inputs are shared, and kept as they are by anyone without the key
+        assert_eq!(cache.read(2024, 6, Some("bob")).as_deref(), Some("1 2 3\n"));
+        assert!(plain.write(2024, 6, "7 8 9\n", "bob").is_err());
+        assert!(!dir.join("y2024/d6.txt").exists());
+        // plain inputs keep working with a key
+        assert_eq!(cache.load(2024, 5).unwrap(), "4 5 6\n");
+        let Err(err) = plain.load(2024, 6) else {
+            panic!("expected the input to need the key");
+        };
+        assert!(err.to_string().contains("ADVENT_CACHE_KEY"));
+        let other = plain
+            .clone()
+            .with_key(Some(CacheKey::from_hex(&"07".repeat(32)).unwrap()));
+        assert!(other.load(2024, 6).is_err());
+        let entries = plain.entries(None).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[1].encrypted && entries[1].problem.is_none());
+        assert!(other.entries(None).unwrap()[1].problem.is_some());
+        assert!(other.write(2024, 6, "7 8 9\n", "bob").is_err());
+        assert_eq!(fs::read(dir.join("y2024/d6.txt.enc")).unwrap(), blob);
+        // a damaged blob is found without the key too
+        fs::write(dir.join("y2024/d6.txt.enc"), &blob[1..]).unwrap();
+        assert!(plain.entries(None).unwrap()[1].problem.is_some());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_poisoned_entries() {
+        let (cache, dir) = cache("poisoned");
+        cache.write(2024, 6, "1 2 3\n", "alice").unwrap();
+        fs::write(dir.join("y2024/d6.txt"), "1 2 4\n").unwrap();
+        fs::create_dir_all(dir.join("y2023")).unwrap();
+        fs::write(dir.join("y2023/d1.txt"), "<!DOCTYPE html>").unwrap();
+        fs::write(dir.join("y2023/d2.txt"), "12\n").unwrap();
+        assert_eq!(cache.read(2024, 6, Some("alice")), None);
+        assert_eq!(cache.read(2023, 1, Some("alice")), None);
+        // cached before metadata was kept
+        assert_eq!(cache.read(2023, 2, Some("alice")).as_deref(), Some("12\n"));
+        let entries = cache.entries(None).unwrap();
+        let problems: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.year, entry.day, entry.problem.as_deref()))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (2023, 1, Some("is an HTML page")),
+                (2023, 2, None),
+                (2024, 6, Some("doesn't match its SHA-256")),
+            ]
+        );
+        assert_eq!(cache.clear(Some(2023), Some(1)).unwrap(), 1);
+        assert_eq!(cache.entries(Some(2023)).unwrap().len(), 1);
+        assert_eq!(cache.clear(None, None).unwrap(), 2);
+        assert!(cache.entries(None).unwrap().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
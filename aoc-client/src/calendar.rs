@@ -0,0 +1,148 @@
+//! When puzzles unlock
+//!
+//! Every puzzle unlocks at midnight US Eastern time, which is UTC-5 in December, so between
+//! 00:00 and 05:00 UTC the latest puzzle is still the one of the previous day.
+
+use std::time::Duration;
+
+use time::{Date, Month, OffsetDateTime, Time, UtcOffset};
+
+/// The first year Advent of Code took place
+pub const FIRST_YEAR: u32 = 2015;
+/// From this year on an event has 12 days instead of 25
+const SHORT_EVENTS_FROM: u32 = 2025;
+
+/// US Eastern time in December, daylight saving time is over by then
+fn eastern() -> UtcOffset {
+    UtcOffset::from_hms(-5, 0, 0).unwrap()
+}
+
+/// Number of puzzle days of the event, `None` before the first one
+pub fn days_in_year(year: u32) -> Option<u8> {
+    match year {
+        year if year < FIRST_YEAR => None,
+        year if year < SHORT_EVENTS_FROM => Some(25),
+        _ => Some(12),
+    }
+}
+
+/// The moment the puzzle of the day unlocks, `None` if the event has no such day
+pub fn unlock_time(year: u32, day: u8) -> Option<OffsetDateTime> {
+    if day == 0 || day > days_in_year(year)? {
+        return None;
+    }
+    let date = Date::from_calendar_date(year as i32, Month::December, day).ok()?;
+    Some(date.with_time(Time::MIDNIGHT).assume_offset(eastern()))
+}
+
+/// Time left before the puzzle unlocks, `None` once it is out
+pub fn time_until_unlock(year: u32, day: u8, now: OffsetDateTime) -> Option<Duration> {
+    let wait = unlock_time(year, day)? - now;
+    wait.is_positive().then(|| wait.unsigned_abs())
+}
+
+/// The year of the latest event and, while it runs, its latest day, both in US Eastern time
+pub fn today(now: OffsetDateTime) -> (u32, Option<u8>) {
+    let eastern = now.to_offset(eastern());
+    let year = eastern.year() as u32;
+    let day = (eastern.month() == Month::December)
+        .then_some(eastern.day())
+        .filter(|day| days_in_year(year).is_some_and(|days| *day <= days));
+    (year, day)
+}
+
+/// The next puzzle to unlock
+pub fn next_unlock(now: OffsetDateTime) -> (u32, u8) {
+    let (year, _) = today(now);
+    (year..)
+        .flat_map(|year| (1..=days_in_year(year).unwrap_or(0)).map(move |day| (year, day)))
+        .find(|(year, day)| time_until_unlock(*year, *day, now).is_some())
+        .unwrap()
+}
+
+/// Formats a wait like `3h 2m 5s`, leaving out leading units that are zero
+pub fn format_wait(wait: Duration) -> String {
+    let seconds = wait.as_secs();
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", seconds),
+        (0, 0, _) => format!("{}m {}s", minutes, seconds),
+        (0, _, _) => format!("{}h {}m {}s", hours, minutes, seconds),
+        _ => format!("{}d {}h {}m {}s", days, hours, minutes, seconds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::PrimitiveDateTime;
+
+    fn at(year: i32, month: Month, day: u8, time: (u8, u8, u8)) -> OffsetDateTime {
+        let date = Date::from_calendar_date(year, month, day).unwrap();
+        let time = Time::from_hms(time.0, time.1, time.2).unwrap();
+        PrimitiveDateTime::new(date, time).assume_utc()
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(
+            unlock_time(2024, 6),
+            Some(at(2024, Month::December, 6, (5, 0, 0)))
+        );
+        assert_eq!(
+            unlock_time(2025, 12),
+            Some(at(2025, Month::December, 12, (5, 0, 0)))
+        );
+        assert_eq!(unlock_time(2025, 13), None);
+        assert_eq!(unlock_time(2014, 1), None);
+    }
+
+    #[test]
+    fn test_today() {
+        // still the 5th in New York
+        assert_eq!(
+            today(at(2024, Month::December, 6, (4, 59, 59))),
+            (2024, Some(5))
+        );
+        assert_eq!(
+            today(at(2024, Month::December, 6, (5, 0, 0))),
+            (2024, Some(6))
+        );
+        assert_eq!(
+            today(at(2024, Month::December, 27, (12, 0, 0))),
+            (2024, None)
+        );
+        assert_eq!(
+            today(at(2025, Month::December, 20, (12, 0, 0))),
+            (2025, None)
+        );
+        assert_eq!(today(at(2025, Month::January, 1, (3, 0, 0))), (2024, None));
+    }
+
+    #[test]
+    fn test_time_until_unlock() {
+        let now = at(2024, Month::December, 6, (2, 0, 0));
+        assert_eq!(
+            time_until_unlock(2024, 6, now),
+            Some(Duration::from_secs(3 * 3600))
+        );
+        assert_eq!(time_until_unlock(2024, 5, now), None);
+        assert_eq!(next_unlock(now), (2024, 6));
+        assert_eq!(
+            next_unlock(at(2025, Month::December, 12, (5, 0, 0))),
+            (2026, 1)
+        );
+    }
+
+    #[test]
+    fn test_format_wait() {
+        assert_eq!(format_wait(Duration::from_secs(42)), "42s");
+        assert_eq!(format_wait(Duration::from_secs(3 * 3600 + 5)), "3h 0m 5s");
+        assert_eq!(format_wait(Duration::from_secs(86400 + 61)), "1d 0h 1m 1s");
+    }
+}
@@ -1,165 +1,619 @@
-use std::collections::HashMap;
-use std::error::Error;
-use std::fs::{self, File};
-use std::io::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use reqwest::header::COOKIE;
+use crate::provider::{PuzzleProvider, Submitted};
+use crate::submit::SubmitOutcome;
+use crate::{
+    AdventOfCode, Answer, Answers, CacheKey, ClientError, Config, Cooldown, Example, InputCache,
+    Ledger, LocalPuzzles, Puzzle, SolutionPart, Throttle, REQUEST_INTERVAL,
+};
 
-use crate::SolutionPart;
-
-type ClientError = Box<dyn Error>;
 type ClientResult = Result<String, ClientError>;
-type Params<'a> = HashMap<&'a str, String>;
+
+/// How many times a puzzle that should be out is asked for again after a 404
+const UNLOCK_RETRIES: u32 = 10;
+
+/// What [`Client::fetch_year`] did for a day
+#[derive(Debug)]
+pub enum Fetched {
+    /// The input was cached already
+    Cached,
+    /// The input was downloaded, with its number of lines
+    Downloaded(usize),
+    /// The input couldn't be downloaded, with the reason
+    Failed(ClientError),
+}
 
 /// Advent of Code client
 ///
 /// This client is used to get input from the Advent of Code website and to submit solutions.
+/// It caches what its [`PuzzleProvider`] returns, adventofcode.com unless `puzzles_dir` is
+/// configured. The provider is only made once something isn't cached, so cached inputs are
+/// served without a session.
 ///
 /// # Example
 ///
 /// ```rust
-/// use aoc_client::Client;
+/// use aoc_client::{Answer, Client};
 /// use aoc_client::SolutionPart;
 ///
 /// fn example() {
 ///   let client = Client::new().unwrap();
 ///   
 ///   let input = client.get_input(&SolutionPart::create(2019, 1, 1)).unwrap();
-///   let result = client.submit_solution(&SolutionPart::create(2019, 1, 1), "solution").unwrap();
-///   println!("{}", result);
+///   let outcome = client.submit_solution(&SolutionPart::create(2019, 1, 1), &Answer::from(42)).unwrap();
+///   println!("{}", outcome);
 /// }
 pub struct Client {
-    session_token: String,
-    client: reqwest::blocking::Client,
-    cache_dir: std::path::PathBuf,
+    /// Made from `config` the first time it is needed
+    provider: OnceLock<Box<dyn PuzzleProvider>>,
+    /// `None` when the provider was given
+    config: Option<Config>,
+    cache_dir: PathBuf,
+    inputs: InputCache,
 }
 
 impl Client {
+    /// Creates a client from the `advent.toml` of the working directory
     pub fn new() -> Result<Self, ClientError> {
+        Self::with_config(&Config::load()?)
+    }
+
+    /// A client of the provider of the configuration, nothing is checked before it is needed
+    pub fn with_config(config: &Config) -> Result<Self, ClientError> {
+        let cache_dir = Self::cache_dir(config)?;
         Ok(Self {
-            cache_dir: Self::cache_dir()?,
-            session_token: Self::session_token()?,
-            client: reqwest::blocking::Client::new(),
+            provider: OnceLock::new(),
+            config: Some(config.clone()),
+            cache_dir,
+            inputs: InputCache::from_config(config),
         })
     }
 
+    /// A client of any provider, caching in the given directory
+    pub fn with_provider(
+        provider: impl PuzzleProvider + 'static,
+        cache_dir: impl Into<PathBuf>,
+    ) -> Self {
+        let cache_dir = cache_dir.into();
+        Self {
+            provider: OnceLock::from(Box::new(provider) as Box<dyn PuzzleProvider>),
+            config: None,
+            inputs: InputCache::new(&cache_dir),
+            cache_dir,
+        }
+    }
+
+    /// Encrypts the inputs it caches with the key
+    pub fn with_cache_key(mut self, key: CacheKey) -> Self {
+        self.inputs = self.inputs.with_key(Some(key));
+        self
+    }
+
+    /// Gets the input, from the cache unless the cached one is damaged, isn't an input or
+    /// belongs to another account
     pub fn get_input(&self, solution_part: &SolutionPart) -> ClientResult {
-        if let Ok(input) = self.get_cached_input(solution_part) {
+        if let Some(input) = self.get_cached_input(solution_part) {
             return Ok(input);
         }
-        let input = self.download_input(solution_part)?;
+        let input = self
+            .provider()?
+            .fetch_input(solution_part.year, solution_part.day)?;
         self.cache_input(solution_part, &input)?;
         Ok(input)
     }
 
-    pub fn submit_solution(&self, solution_part: &SolutionPart, solution: &str) -> ClientResult {
-        use select::document::Document;
-        use select::predicate::Name;
-        let SolutionPart { year, day, part } = solution_part;
+    /// Downloads the input of every unlocked day of the year that isn't cached yet
+    ///
+    /// Cached days are skipped, so running it again after an interruption picks up where it
+    /// stopped. `on_day` is told about every day once it is done. A day that fails doesn't
+    /// stop the others, unless the session or the connection is the problem.
+    pub fn fetch_year(
+        &self,
+        year: u32,
+        mut on_day: impl FnMut(u8, &Fetched),
+    ) -> Result<(), ClientError> {
+        for day in self.unlocked_days(year)? {
+            let solution_part = SolutionPart::create(year, day, 1);
+            let fetched = match self.get_cached_input(&solution_part) {
+                Some(_) => Fetched::Cached,
+                None => match self.get_input(&solution_part) {
+                    Ok(input) => Fetched::Downloaded(input.lines().count()),
+                    Err(err @ (ClientError::SessionExpired | ClientError::Network(_))) => {
+                        return Err(err)
+                    }
+                    Err(err) => Fetched::Failed(err),
+                },
+            };
+            on_day(day, &fetched);
+        }
+        Ok(())
+    }
 
-        let url = format!("https://adventofcode.com/{}/day/{}/answer", year, day);
-        let mut params = Params::new();
-        params.insert("level", part.to_string());
-        params.insert("answer", solution.into());
+    /// The days of the year whose puzzle is out, as the provider tells
+    pub fn unlocked_days(&self, year: u32) -> Result<Vec<u8>, ClientError> {
+        let provider = self.provider()?;
+        let mut days = provider.days(year)?;
+        days.retain(|day| provider.time_until_unlock(year, *day).is_none());
+        Ok(days)
+    }
 
-        let response = self.post_request(&url, &params)?;
+    /// Submits the answer, unless it fails the sanity checks of [`Answer::submission`] or the
+    /// [`Ledger`] of the part rules it out
+    ///
+    /// While a [`Cooldown`] started by an earlier response is running nothing is sent, the
+    /// outcome is [`SubmitOutcome::RateLimited`] with the time left.
+    pub fn submit_solution(
+        &self,
+        solution_part: &SolutionPart,
+        answer: &Answer,
+    ) -> Result<SubmitOutcome, ClientError> {
+        let SolutionPart { year, day, part } = solution_part;
+        let solution = answer.submission().map_err(ClientError::NotSubmitted)?;
+        let mut ledger = self.submissions(solution_part)?;
+        ledger.check(&solution).map_err(ClientError::NotSubmitted)?;
+        let cooldown_timer = Cooldown::new(&self.cache_dir);
+        if let Some(wait) = cooldown_timer.remaining() {
+            return Ok(SubmitOutcome::RateLimited { wait });
+        }
 
-        let doc = Document::from(response.as_str());
-        let node = doc.find(Name("main")).next().unwrap();
-        let text = node.text();
-        let text = format!("{}.", text.trim());
-        Ok(text)
+        let Submitted { outcome, cooldown } = self.provider()?.submit(solution_part, &solution)?;
+        if let Some(wait) = cooldown {
+            cooldown_timer.start(wait)?;
+        }
+        ledger.record(&solution, outcome.clone())?;
+        if outcome == SubmitOutcome::Correct {
+            self.accepted_answers(*year)?
+                .record_part(*day, *part, &solution)?;
+        }
+        Ok(outcome)
     }
 
-    fn cache_dir() -> Result<std::path::PathBuf, ClientError> {
-        let cache_dir = Self::root_folder()?.join(".data");
-        fs::create_dir_all(&cache_dir)?;
-        Ok(cache_dir)
+    /// Same as [`Client::submit_solution`], waiting out rate limits and submitting again
+    ///
+    /// `on_wait` is called with the time left about once a second, to show a countdown.
+    pub fn submit_waiting(
+        &self,
+        solution_part: &SolutionPart,
+        answer: &Answer,
+        mut on_wait: impl FnMut(Duration),
+    ) -> Result<SubmitOutcome, ClientError> {
+        loop {
+            match self.submit_solution(solution_part, answer)? {
+                SubmitOutcome::RateLimited { wait } => countdown(wait, &mut on_wait),
+                outcome => return Ok(outcome),
+            }
+        }
     }
 
-    fn session_token() -> ClientResult {
-        let folder_path = Self::root_folder()?.join("aoc-client/.session");
-        let token = std::fs::read_to_string(folder_path)?.trim().to_string();
-        Ok(token)
+    /// Waits for the puzzle to unlock and gets its input as soon as it is out
+    ///
+    /// `on_wait` is called with the time left about once a second, to show a countdown. The
+    /// server may take a moment longer than the provider said, so a 404 is retried a few times.
+    pub fn wait_for_input(
+        &self,
+        solution_part: &SolutionPart,
+        mut on_wait: impl FnMut(Duration),
+    ) -> ClientResult {
+        let SolutionPart { year, day, .. } = *solution_part;
+        if let Some(wait) = self.provider()?.time_until_unlock(year, day) {
+            countdown(wait, &mut on_wait);
+        }
+        let mut attempts = 0;
+        loop {
+            match self.get_input(solution_part) {
+                Err(ClientError::NotUnlocked { .. }) if attempts < UNLOCK_RETRIES => {
+                    attempts += 1;
+                    thread::sleep(Duration::from_secs(1));
+                }
+                result => return result,
+            }
+        }
     }
 
-    fn root_folder() -> Result<std::path::PathBuf, ClientError> {
-        let file = std::env::current_exe()?;
-        let mut base = file.parent().unwrap().parent().unwrap().parent().unwrap();
-        if base.to_str().unwrap().ends_with("target") {
-            base = base.parent().unwrap();
+    /// Gets the page of the day, cached beside the input
+    ///
+    /// Part 2 and the accepted answers only show up as parts are solved, so a cached page is
+    /// downloaded again until both parts are, falling back to the cached page if that fails.
+    pub fn get_puzzle(&self, year: u32, day: u8) -> Result<Puzzle, ClientError> {
+        let path = self.cache_dir.join(format!("y{}/d{}.html", year, day));
+        let cached = fs::read_to_string(&path)
+            .ok()
+            .map(|html| Puzzle::new(year, day, html));
+        if let Some(puzzle) = cached.as_ref().filter(|puzzle| puzzle.is_complete()) {
+            return Ok(puzzle.clone());
+        }
+        let fetched = self
+            .provider()
+            .and_then(|provider| provider.fetch_description(year, day));
+        match (fetched, cached) {
+            (Ok(html), _) => {
+                fs::create_dir_all(path.parent().unwrap())?;
+                fs::write(&path, &html)?;
+                Ok(Puzzle::new(year, day, html))
+            }
+            (Err(_), Some(cached)) => Ok(cached),
+            (Err(err), None) => Err(err),
         }
-        Ok(base.to_path_buf())
     }
 
-    fn get_cached_input(&self, solution_part: &SolutionPart) -> ClientResult {
-        let path = self.solution_path(solution_part);
-        Ok(std::fs::read_to_string(path)?)
+    /// Saves the examples of the puzzle page and returns every saved example of the day
+    ///
+    /// Examples saved before are kept as they are, they may have been corrected by hand.
+    pub fn get_examples(&self, year: u32, day: u8) -> Result<Vec<Example>, ClientError> {
+        for example in self.get_puzzle(year, day)?.examples() {
+            example.save(&self.cache_dir, year, day)?;
+        }
+        self.saved_examples(year, day)
     }
 
-    fn cache_input(&self, solution_part: &SolutionPart, input: &str) -> Result<(), ClientError> {
-        let path = self.solution_path(solution_part);
-        fs::create_dir_all(path.parent().unwrap())?;
-        let mut file = File::create(path)?;
-        file.write_all(input.as_bytes())?;
-        Ok(())
+    /// The examples of the day saved so far
+    pub fn saved_examples(&self, year: u32, day: u8) -> Result<Vec<Example>, ClientError> {
+        Example::load(&self.cache_dir, year, day)
     }
 
-    fn solution_path(&self, solution_part: &SolutionPart) -> std::path::PathBuf {
-        let path = format!("y{}/d{}.txt", solution_part.year, solution_part.day);
-        self.cache_dir.join(path)
+    /// Records the answers accepted for the day from its page, and returns them
+    pub fn get_answers(&self, year: u32, day: u8) -> Result<Vec<String>, ClientError> {
+        let answers = self.get_puzzle(year, day)?.answers();
+        self.accepted_answers(year)?.record(day, answers.clone())?;
+        Ok(answers)
     }
 
-    fn download_input(&self, solution_part: &SolutionPart) -> ClientResult {
-        let SolutionPart { year, day, .. } = solution_part;
-        let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
-        let cookie = format!("session={}", self.session_token);
-        let input = self.client.get(&url).header(COOKIE, cookie).send()?;
-        let input = input.error_for_status()?.text()?;
-        Ok(input)
+    /// The answers recorded as accepted for the days of the year
+    pub fn accepted_answers(&self, year: u32) -> Result<Answers, ClientError> {
+        Answers::load(&self.cache_dir, year)
     }
 
-    fn post_request(&self, url: &str, params: &Params) -> ClientResult {
-        let cookie = format!("session={}", self.session_token);
-        let req = self.client.post(url).header(COOKIE, cookie).form(&params);
-        let response = req.send()?.error_for_status()?.text()?;
-        Ok(response)
+    /// The answers submitted so far for the part
+    pub fn submissions(&self, solution_part: &SolutionPart) -> Result<Ledger, ClientError> {
+        Ledger::load(&self.cache_dir, solution_part)
+    }
+
+    fn cache_dir(config: &Config) -> Result<PathBuf, ClientError> {
+        let cache_dir = config.cache_dir.clone();
+        fs::create_dir_all(&cache_dir)?;
+        Ok(cache_dir)
+    }
+
+    /// The provider, made the first time it is needed
+    ///
+    /// Requests to adventofcode.com are throttled through [`Config::throttle_file`], shared
+    /// by every profile and process of the project.
+    fn provider(&self) -> Result<&dyn PuzzleProvider, ClientError> {
+        if let Some(provider) = self.provider.get() {
+            return Ok(provider.as_ref());
+        }
+        let config = self
+            .config
+            .as_ref()
+            .expect("a client without a provider has a configuration");
+        let provider: Box<dyn PuzzleProvider> = match &config.puzzles_dir {
+            Some(puzzles_dir) => Box::new(LocalPuzzles::new(puzzles_dir)),
+            None => Box::new(
+                AdventOfCode::new(Self::session_token(config)?)
+                    .with_throttle(Throttle::new(config.throttle_file(), REQUEST_INTERVAL)),
+            ),
+        };
+        Ok(self.provider.get_or_init(|| provider).as_ref())
+    }
+
+    fn session_token(config: &Config) -> ClientResult {
+        Ok(config.session()?.token)
+    }
+
+    /// Without a provider, like adventofcode.com without a session, any cached input will do
+    fn get_cached_input(&self, solution_part: &SolutionPart) -> Option<String> {
+        let SolutionPart { year, day, .. } = *solution_part;
+        let account = self.provider().ok().map(|provider| provider.account());
+        self.inputs.read(year, day, account.as_deref())
+    }
+
+    fn cache_input(&self, solution_part: &SolutionPart, input: &str) -> Result<(), ClientError> {
+        let SolutionPart { year, day, .. } = *solution_part;
+        self.inputs
+            .write(year, day, input, &self.provider()?.account())
+    }
+}
+
+/// Calls `on_wait` with the time left about once a second until the wait is over
+fn countdown(wait: Duration, on_wait: &mut impl FnMut(Duration)) {
+    let end = Instant::now() + wait;
+    loop {
+        let remaining = end.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return;
+        }
+        on_wait(remaining);
+        thread::sleep(remaining.min(Duration::from_secs(1)));
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stub::StubServer;
+    use crate::Hint;
+
+    /// A client of the stub with its own cache, removed when the test is done
+    fn stub_client(server: &StubServer, name: &str) -> (Client, PathBuf) {
+        let root = std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let provider = AdventOfCode::new("token").with_base_url(&server.url);
+        let client = Client::with_provider(provider, root.join(".data"));
+        (client, root)
+    }
+
+    fn page(text: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn test_get_puzzle_is_cached() {
+        let server = StubServer::start(vec![
+            (
+                200,
+                include_str!("../fixtures/puzzle/part1.html").to_string(),
+            ),
+            (
+                200,
+                include_str!("../fixtures/puzzle/solved.html").to_string(),
+            ),
+        ]);
+        let (client, root) = stub_client(&server, "puzzle");
+        assert_eq!(client.get_puzzle(2024, 6).unwrap().parts(), 1);
+        assert!(root.join(".data/y2024/d6.html").is_file());
+        // only part 1 was cached, so the page is downloaded again
+        assert_eq!(client.get_puzzle(2024, 6).unwrap().parts(), 2);
+        assert_eq!(client.get_puzzle(2024, 6).unwrap().parts(), 2);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].line, "GET /2024/day/6 HTTP/1.1");
+        let examples = client.get_examples(2024, 6).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert!(root.join(".data/examples/y2024/d6_2.txt").is_file());
+        assert_eq!(client.get_answers(2024, 6).unwrap(), vec!["4819", "1796"]);
+        let accepted = client.accepted_answers(2024).unwrap();
+        assert_eq!(accepted.get(6, 2), Some("1796"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_missing_session() {
+        let root = std::env::temp_dir().join(format!("aoc-client-session-{}", std::process::id()));
+        let mut config = Config::with_root(root.clone());
+        config.session_file = PathBuf::from("/nonexistent/.session");
+        let client = Client::with_config(&config).unwrap();
+        // a cached input is served without a session, whoever it was downloaded for
+        InputCache::from_config(&config)
+            .write(2024, 7, "1 2 3\n", "session 0123456789ab")
+            .unwrap();
+        let input = client.get_input(&SolutionPart::create(2024, 7, 1));
+        assert_eq!(input.unwrap(), "1 2 3\n");
+        let Err(err) = client.get_input(&SolutionPart::create(2024, 8, 1)) else {
+            panic!("expected a missing session file");
+        };
+        assert!(matches!(err, ClientError::MissingSession { .. }));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_submit_starts_cooldown() {
+        let server = StubServer::start(vec![(
+            200,
+            page("That's not the right answer; your answer is too low. Please wait one minute before trying again."),
+        )]);
+        let (client, root) = stub_client(&server, "cooldown");
+        let solution = SolutionPart::create(2024, 7, 1);
+        let outcome = client
+            .submit_solution(&solution, &Answer::from(10))
+            .unwrap();
+        assert_eq!(outcome, SubmitOutcome::Incorrect { hint: Hint::TooLow });
+        let requests = server.requests();
+        assert_eq!(requests[0].line, "POST /2024/day/7/answer HTTP/1.1");
+        assert!(requests[0].body.contains("answer=10"));
+        // another process sharing the cache waits too, without sending anything
+        let outcome = client
+            .submit_solution(&solution, &Answer::from(20))
+            .unwrap();
+        assert!(matches!(outcome, SubmitOutcome::RateLimited { .. }));
+        assert_eq!(server.requests().len(), 1);
+        assert!(client.submit_solution(&solution, &Answer::from(5)).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_submit_waiting_retries() {
+        let server = StubServer::start(vec![
+            (
+                200,
+                page("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1s left to wait."),
+            ),
+            (200, page("That's the right answer! You are one gold star closer.")),
+        ]);
+        let (client, root) = stub_client(&server, "waiting");
+        let solution = SolutionPart::create(2024, 7, 2);
+        let mut waits = vec![];
+        let outcome = client
+            .submit_waiting(&solution, &Answer::from(42), |wait| waits.push(wait))
+            .unwrap();
+        assert_eq!(outcome, SubmitOutcome::Correct);
+        assert!(!waits.is_empty());
+        assert_eq!(server.requests().len(), 2);
+        let ledger = client.submissions(&solution).unwrap();
+        let outcomes: Vec<_> = ledger.submissions.iter().map(|s| &s.outcome).collect();
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[1], &SubmitOutcome::Correct);
+        let accepted = client.accepted_answers(2024).unwrap();
+        assert_eq!(accepted.get(7, 2), Some("42"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_local_provider() {
+        let root = std::env::temp_dir().join(format!("aoc-client-local-{}", std::process::id()));
+        fs::create_dir_all(root.join("puzzles/y2024")).unwrap();
+        fs::write(root.join("puzzles/y2024/d30.txt"), "1 2 3\n").unwrap();
+        fs::write(root.join("puzzles/y2024/d30_1.answer"), "6\n").unwrap();
+        let mut config = Config::with_root(root.clone());
+        config.puzzles_dir = Some(root.join("puzzles"));
+        config.session_file = root.join("no-session");
+        let client = Client::with_config(&config).unwrap();
+        let solution = SolutionPart::create(2024, 30, 1);
+        assert_eq!(client.get_input(&solution).unwrap(), "1 2 3\n");
+        // a local set isn't bound to the calendar of adventofcode.com
+        assert_eq!(client.unlocked_days(2024).unwrap(), vec![30]);
+        let mut days = vec![];
+        client
+            .fetch_year(2024, |day, fetched| {
+                days.push((day, format!("{:?}", fetched)))
+            })
+            .unwrap();
+        assert_eq!(days, vec![(30, "Cached".to_string())]);
+        assert!(root.join(".data/y2024/d30.txt").is_file());
+        let outcome = client.submit_solution(&solution, &Answer::from(6)).unwrap();
+        assert_eq!(outcome, SubmitOutcome::Correct);
+        assert_eq!(client.accepted_answers(2024).unwrap().get(30, 1), Some("6"));
+        fs::remove_dir_all(root).unwrap();
+    }
 
     #[test]
     fn test_get_input() {
-        let client = Client::new().unwrap();
+        let input = include_str!("../fixtures/input/y2023_d6.txt");
+        let server = StubServer::start(vec![(200, input.to_string())]);
+        let (client, root) = stub_client(&server, "input");
         let solution = SolutionPart::create(2023, 6, 1);
-        let result = client.get_input(&solution).unwrap();
-        assert_eq!(
-            result,
-            "Time:        44     80     65     72\nDistance:   208   1581   1050   1102\n"
-        );
+        assert_eq!(client.get_input(&solution).unwrap(), input);
+        let requests = server.requests();
+        assert_eq!(requests[0].line, "GET /2023/day/6/input HTTP/1.1");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=token"));
+        assert_eq!(requests[0].user_agent.as_deref(), Some(crate::USER_AGENT));
+        fs::remove_dir_all(root).unwrap();
     }
 
     #[test]
     fn test_input_is_cached() {
-        let client = Client::new().unwrap();
+        let input = include_str!("../fixtures/input/y2023_d6.txt");
+        let server = StubServer::start(vec![(200, input.to_string())]);
+        let (client, root) = stub_client(&server, "cached");
         let solution = SolutionPart::create(2023, 6, 1);
         client.get_input(&solution).unwrap();
-        let result = client.get_cached_input(&solution).unwrap();
+        assert_eq!(client.get_cached_input(&solution).unwrap(), input);
+        assert_eq!(client.get_input(&solution).unwrap(), input);
+        assert_eq!(server.requests().len(), 1);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_year() {
+        let input = include_str!("../fixtures/input/y2023_d6.txt");
+        let mut responses = vec![(404, "404 Not Found".to_string())];
+        responses.extend((4..=25).map(|_| (200, input.to_string())));
+        let server = StubServer::start(responses);
+        let (client, root) = stub_client(&server, "year");
+        for day in 1..=2 {
+            client
+                .cache_input(&SolutionPart::create(2015, day, 1), input)
+                .unwrap();
+        }
+        let mut days = vec![];
+        client
+            .fetch_year(2015, |day, fetched| {
+                days.push((day, format!("{:?}", fetched)))
+            })
+            .unwrap();
+        assert_eq!(days.len(), 25);
+        assert_eq!(days[1], (2, "Cached".to_string()));
+        assert!(days[2].1.starts_with("Failed(NotUnlocked"));
         assert_eq!(
-            result,
-            "Time:        44     80     65     72\nDistance:   208   1581   1050   1102\n"
+            days[24],
+            (25, format!("Downloaded({})", input.lines().count()))
         );
+        assert_eq!(server.requests()[0].line, "GET /2015/day/3/input HTTP/1.1");
+
+        // only day 3 is left, and an expired session stops everything
+        let server = StubServer::start(vec![(400, "".to_string())]);
+        let provider = AdventOfCode::new("token").with_base_url(&server.url);
+        let client = Client::with_provider(provider, root.join(".data"));
+        let mut days = vec![];
+        let err = client
+            .fetch_year(2015, |day, _| days.push(day))
+            .unwrap_err();
+        assert!(matches!(err, ClientError::SessionExpired));
+        assert_eq!(days, vec![1, 2]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_poisoned_input_is_downloaded_again() {
+        let input = include_str!("../fixtures/input/y2023_d6.txt");
+        let server = StubServer::start(vec![
+            (
+                200,
+                "Please don't repeatedly request this endpoint before it unlocks!".to_string(),
+            ),
+            (200, input.to_string()),
+        ]);
+        let (client, root) = stub_client(&server, "poisoned");
+        let solution = SolutionPart::create(2023, 6, 1);
+        assert!(matches!(
+            client.get_input(&solution),
+            Err(ClientError::Cache(_))
+        ));
+        // an error page cached by an older version
+        fs::create_dir_all(root.join(".data/y2023")).unwrap();
+        fs::write(root.join(".data/y2023/d6.txt"), "<!DOCTYPE html>").unwrap();
+        assert_eq!(client.get_input(&solution).unwrap(), input);
+        assert!(root.join(".data/y2023/d6.meta.json").is_file());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_encrypted_cache() {
+        let input = include_str!("../fixtures/input/y2023_d6.txt");
+        let server = StubServer::start(vec![(200, input.to_string())]);
+        let (client, root) = stub_client(&server, "encrypted");
+        let client = client.with_cache_key(CacheKey::from_hex(&"5e".repeat(32)).unwrap());
+        let solution = SolutionPart::create(2023, 6, 1);
+        assert_eq!(client.get_input(&solution).unwrap(), input);
+        assert_eq!(client.get_input(&solution).unwrap(), input);
+        assert_eq!(server.requests().len(), 1);
+        assert!(root.join(".data/y2023/d6.txt.enc").is_file());
+        assert!(!root.join(".data/y2023/d6.txt").exists());
+        fs::remove_dir_all(root).unwrap();
     }
 
     #[test]
     fn test_caching() {
+        let server = StubServer::start(vec![]);
+        let (client, root) = stub_client(&server, "caching");
         let solution = SolutionPart::create(1000, 100, 1);
-        let client = Client::new().unwrap();
         client.cache_input(&solution, "test").unwrap();
         let res = client.get_cached_input(&solution).unwrap();
         assert_eq!(res, "test");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_submit_rate_limited() {
+        let server = StubServer::start(vec![(
+            200,
+            include_str!("../fixtures/submit/rate_limited.html").to_string(),
+        )]);
+        let (client, root) = stub_client(&server, "limited");
+        let outcome = client
+            .submit_solution(&SolutionPart::create(2024, 7, 1), &Answer::from(10))
+            .unwrap();
+        let SubmitOutcome::RateLimited { wait } = outcome else {
+            panic!("expected a rate limit, got {:?}", outcome);
+        };
+        let remaining = Cooldown::new(&root.join(".data")).remaining().unwrap();
+        assert!(remaining <= wait);
+        fs::remove_dir_all(root).unwrap();
     }
 }
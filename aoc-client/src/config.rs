@@ -0,0 +1,377 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::{CacheKey, ClientError, Session, SESSION_VAR};
+
+/// Name of the configuration file looked up from the working directory upwards
+pub const CONFIG_FILE: &str = "advent.toml";
+
+/// What to do with an answer after running a solution
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmitMode {
+    /// Ask before submitting
+    #[default]
+    Ask,
+    /// Submit without asking
+    Always,
+    /// Only submit when asked to on the command line
+    Never,
+}
+
+impl std::str::FromStr for SubmitMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ask" => Ok(SubmitMode::Ask),
+            "always" => Ok(SubmitMode::Always),
+            "never" => Ok(SubmitMode::Never),
+            _ => Err(format!(
+                "invalid submit mode {}, expected ask, always or never",
+                s
+            )),
+        }
+    }
+}
+
+/// The `advent.toml` file as written, every field optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    year: Option<u32>,
+    session_file: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    puzzles_dir: Option<PathBuf>,
+    cache_key: Option<String>,
+    submit: Option<SubmitMode>,
+    format: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// Another account, with its own session and cache
+///
+/// The session file defaults to `aoc-client/.session-NAME` and the cache to `profiles/NAME`
+/// in the cache directory.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub session_file: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// Project configuration shared by the runner and the client
+///
+/// Read from the first `advent.toml` found walking up from the working directory, or from the
+/// file in `ADVENT_CONFIG`. Every field can be overridden with an `ADVENT_*` environment
+/// variable, and the session token itself can be given in `ADVENT_SESSION`. Relative paths are
+/// relative to the directory of the configuration file.
+///
+/// ```toml
+/// year = 2024
+/// session_file = "aoc-client/.session"
+/// cache_dir = ".data"
+/// puzzles_dir = "puzzles"
+/// cache_key = "5e2b0c..." # 32 random bytes in hex
+/// submit = "ask"
+/// format = "text"
+///
+/// [profiles.alice]
+/// session_file = "aoc-client/.session-alice"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Directory of the configuration file, relative paths are resolved against it
+    pub root: PathBuf,
+    /// Default puzzle year
+    pub year: Option<u32>,
+    pub session_file: PathBuf,
+    /// Session token from `ADVENT_SESSION`, used instead of the session file
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+    /// Serve puzzles from this directory instead of adventofcode.com, see [`LocalPuzzles`]
+    ///
+    /// [`LocalPuzzles`]: crate::LocalPuzzles
+    pub puzzles_dir: Option<PathBuf>,
+    /// Key to encrypt cached inputs with, see [`InputCache`]
+    ///
+    /// [`InputCache`]: crate::InputCache
+    pub cache_key: Option<CacheKey>,
+    pub submit: SubmitMode,
+    /// Default report format, interpreted by the runner
+    pub format: Option<String>,
+    /// The other accounts by name, see [`Config::profile`]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Name of the profile this configuration is for, `None` for the default account
+    pub profile: Option<String>,
+}
+
+impl Config {
+    /// Loads the configuration for the working directory
+    pub fn load() -> Result<Self, ClientError> {
+        let path = match std::env::var_os("ADVENT_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => Self::find(&std::env::current_dir()?),
+        };
+        let mut config = match path {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::with_root(Self::fallback_root()?),
+        };
+        config.apply_overrides(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Finds the closest configuration file in `start` or one of its parents
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// The defaults, relative to the given directory
+    pub fn with_root(root: PathBuf) -> Self {
+        Self {
+            session_file: root.join("aoc-client/.session"),
+            session: None,
+            cache_dir: root.join(".data"),
+            puzzles_dir: None,
+            cache_key: None,
+            root,
+            year: None,
+            submit: SubmitMode::default(),
+            format: None,
+            profiles: BTreeMap::new(),
+            profile: None,
+        }
+    }
+
+    /// The configuration of a profile, the same but for its session and cache
+    pub fn profile(&self, name: &str) -> Result<Self, ClientError> {
+        let profile = self.profiles.get(name).ok_or_else(|| {
+            let names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            ClientError::Config(match names.is_empty() {
+                true => format!(
+                    "no profile {}, there are no profiles in {}",
+                    name, CONFIG_FILE
+                ),
+                false => format!("no profile {}, the profiles are {}", name, names.join(", ")),
+            })
+        })?;
+        let mut config = self.clone();
+        config.session_file = match &profile.session_file {
+            Some(session_file) => self.root.join(session_file),
+            None => self.root.join(format!("aoc-client/.session-{}", name)),
+        };
+        config.cache_dir = match &profile.cache_dir {
+            Some(cache_dir) => self.root.join(cache_dir),
+            None => self.cache_dir.join("profiles").join(name),
+        };
+        // the token of the environment is the one of the default account
+        config.session = None;
+        config.profile = Some(name.to_string());
+        Ok(config)
+    }
+
+    /// The configuration of the default account followed by those of every profile
+    pub fn all_profiles(&self) -> Result<Vec<Self>, ClientError> {
+        let mut configs = vec![self.clone()];
+        for name in self.profiles.keys() {
+            configs.push(self.profile(name)?);
+        }
+        Ok(configs)
+    }
+
+    /// The session of the account, from `ADVENT_SESSION` or the session file
+    pub fn session(&self) -> Result<Session, ClientError> {
+        match &self.session {
+            Some(token) => Ok(Session::new(token)),
+            None => Session::load(&self.session_file),
+        }
+    }
+
+    /// Lock file spacing out the requests of every profile and process of the project
+    pub fn throttle_file(&self) -> PathBuf {
+        self.root.join(".advent-throttle")
+    }
+
+    /// Name of the profile, `default` for the default account
+    pub fn profile_name(&self) -> &str {
+        self.profile.as_deref().unwrap_or("default")
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ClientError> {
+        let text = fs::read_to_string(path).map_err(|err| {
+            ClientError::Config(format!("could not read {}: {}", path.display(), err))
+        })?;
+        let root = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        Self::parse(&text, root)
+            .map_err(|err| ClientError::Config(format!("{}: {}", path.display(), err)))
+    }
+
+    fn parse(text: &str, root: PathBuf) -> Result<Self, ClientError> {
+        let file: ConfigFile =
+            toml::from_str(text).map_err(|err| ClientError::Config(err.to_string()))?;
+        let mut config = Self::with_root(root);
+        if let Some(session_file) = file.session_file {
+            config.session_file = config.root.join(session_file);
+        }
+        if let Some(cache_dir) = file.cache_dir {
+            config.cache_dir = config.root.join(cache_dir);
+        }
+        config.puzzles_dir = file.puzzles_dir.map(|dir| config.root.join(dir));
+        config.cache_key = file.cache_key.as_deref().map(parse_cache_key).transpose()?;
+        config.year = file.year;
+        config.submit = file.submit.unwrap_or_default();
+        config.format = file.format;
+        config.profiles = file.profiles;
+        Ok(config)
+    }
+
+    /// Applies the `ADVENT_*` variables returned by `var`
+    fn apply_overrides(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), ClientError> {
+        if let Some(year) = var("ADVENT_YEAR") {
+            self.year = Some(
+                year.parse()
+                    .map_err(|_| ClientError::Config(format!("invalid ADVENT_YEAR {}", year)))?,
+            );
+        }
+        if let Some(session_file) = var("ADVENT_SESSION_FILE") {
+            self.session_file = self.root.join(session_file);
+        }
+        if let Some(session) = var(SESSION_VAR) {
+            self.session = Some(session);
+        }
+        if let Some(cache_dir) = var("ADVENT_CACHE_DIR") {
+            self.cache_dir = self.root.join(cache_dir);
+        }
+        if let Some(puzzles_dir) = var("ADVENT_PUZZLES_DIR") {
+            self.puzzles_dir = Some(self.root.join(puzzles_dir));
+        }
+        if let Some(cache_key) = var("ADVENT_CACHE_KEY") {
+            self.cache_key = Some(parse_cache_key(&cache_key)?);
+        }
+        if let Some(submit) = var("ADVENT_SUBMIT") {
+            self.submit = submit.parse().map_err(ClientError::Config)?;
+        }
+        if let Some(format) = var("ADVENT_FORMAT") {
+            self.format = Some(format);
+        }
+        Ok(())
+    }
+
+    /// The checkout the binary was built in, for when there is no configuration file
+    fn fallback_root() -> Result<PathBuf, ClientError> {
+        let file = std::env::current_exe()?;
+        let mut base = file
+            .ancestors()
+            .nth(3)
+            .ok_or_else(|| ClientError::Config("could not find the project folder".into()))?;
+        if base.ends_with("target") {
+            base = base.parent().unwrap_or(base);
+        }
+        Ok(base.to_path_buf())
+    }
+}
+
+fn parse_cache_key(hex: &str) -> Result<CacheKey, ClientError> {
+    CacheKey::from_hex(hex).map_err(ClientError::Config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "year = 2023\nsession_file = \"secrets/session\"\nsubmit = \"never\"\n";
+        let config = Config::parse(text, PathBuf::from("/project")).unwrap();
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(
+            config.session_file,
+            PathBuf::from("/project/secrets/session")
+        );
+        assert_eq!(config.cache_dir, PathBuf::from("/project/.data"));
+        assert_eq!(config.submit, SubmitMode::Never);
+        assert_eq!(config.format, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_fields() {
+        assert!(Config::parse("yaer = 2023", PathBuf::from("/project")).is_err());
+        assert!(Config::parse("submit = \"maybe\"", PathBuf::from("/project")).is_err());
+    }
+
+    #[test]
+    fn test_absolute_paths_are_kept() {
+        let config = Config::parse("cache_dir = \"/tmp/aoc\"", PathBuf::from("/project")).unwrap();
+        assert_eq!(config.cache_dir, PathBuf::from("/tmp/aoc"));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut config = Config::with_root(PathBuf::from("/project"));
+        config
+            .apply_overrides(|name| match name {
+                "ADVENT_YEAR" => Some("2022".to_string()),
+                "ADVENT_CACHE_DIR" => Some("cache".to_string()),
+                "ADVENT_FORMAT" => Some("json".to_string()),
+                "ADVENT_SESSION" => Some("token".to_string()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(config.session().unwrap().token, "token");
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.cache_dir, PathBuf::from("/project/cache"));
+        assert_eq!(config.format.as_deref(), Some("json"));
+        assert!(config
+            .apply_overrides(|name| (name == "ADVENT_YEAR").then(|| "soon".to_string()))
+            .is_err());
+        assert!(config
+            .apply_overrides(|name| (name == "ADVENT_CACHE_KEY").then(|| "team".to_string()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_profiles() {
+        let text = "cache_dir = \"cache\"\n\n[profiles.alice]\n\n[profiles.bob]\nsession_file = \"bob\"\ncache_dir = \"/tmp/bob\"\n";
+        let config = Config::parse(text, PathBuf::from("/project")).unwrap();
+        let alice = config.profile("alice").unwrap();
+        assert_eq!(
+            alice.session_file,
+            PathBuf::from("/project/aoc-client/.session-alice")
+        );
+        assert_eq!(
+            alice.cache_dir,
+            PathBuf::from("/project/cache/profiles/alice")
+        );
+        assert_eq!(alice.profile_name(), "alice");
+        let bob = config.profile("bob").unwrap();
+        assert_eq!(bob.session_file, PathBuf::from("/project/bob"));
+        assert_eq!(bob.cache_dir, PathBuf::from("/tmp/bob"));
+        let names: Vec<String> = config
+            .all_profiles()
+            .unwrap()
+            .iter()
+            .map(|config| config.profile_name().to_string())
+            .collect();
+        assert_eq!(names, ["default", "alice", "bob"]);
+        let err = config.profile("carol").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no profile carol, the profiles are alice, bob"
+        );
+        assert!(Config::parse("[profiles.alice]\nyear = 2023", PathBuf::from("/project")).is_err());
+    }
+
+    #[test]
+    fn test_find() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let found = Config::find(Path::new(env!("CARGO_MANIFEST_DIR")).join("src").as_path());
+        assert_eq!(found, Some(root.join(CONFIG_FILE)));
+    }
+}
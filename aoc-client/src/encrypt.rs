@@ -0,0 +1,105 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+/// Start of every encrypted blob, to tell the format apart from anything else
+const MAGIC: &[u8] = b"advent-enc-1\n";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// The key cached inputs are encrypted with, 32 random bytes shared by the team
+///
+/// Blobs are ChaCha20-Poly1305 encrypted, so a blob that was tampered with or encrypted with
+/// another key fails to decrypt instead of giving garbage. The key isn't derived from a
+/// passphrase, as committed blobs would let anyone guess a passphrase offline.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CacheKey(Key);
+
+impl CacheKey {
+    /// Reads the key from hex, as printed by `openssl rand -hex 32`
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let hex = hex.trim();
+        let invalid = || {
+            format!(
+                "the cache key must be {} random bytes in hex, `openssl rand -hex {}` makes one",
+                KEY_LEN, KEY_LEN
+            )
+        };
+        if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+        Ok(Self(Key::clone_from_slice(&bytes)))
+    }
+
+    /// Encrypts the text, bound to `context` so a blob can't be passed off as another one
+    pub fn seal(&self, context: &str, text: &str) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: text.as_bytes(),
+            aad: context.as_bytes(),
+        };
+        let ciphertext = ChaCha20Poly1305::new(&self.0)
+            .encrypt(&nonce, payload)
+            .expect("encrypting in memory can't fail");
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    /// Decrypts a blob made by [`CacheKey::seal`] with the same key and context
+    pub fn open(&self, context: &str, blob: &[u8]) -> Result<String, String> {
+        let blob = blob
+            .strip_prefix(MAGIC)
+            .filter(|blob| blob.len() >= NONCE_LEN)
+            .ok_or("is not an encrypted input")?;
+        let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: context.as_bytes(),
+        };
+        let text = ChaCha20Poly1305::new(&self.0)
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| "can't be decrypted with the configured cache key")?;
+        String::from_utf8(text).map_err(|_| "is not text once decrypted".to_string())
+    }
+}
+
+impl std::fmt::Debug for CacheKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CacheKey(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = CacheKey::from_hex(&"5e".repeat(32)).unwrap();
+        let blob = key.seal("y2024/d6", "1 2 3\n");
+        assert!(!blob.windows(5).any(|window| window == b"1 2 3"));
+        assert_eq!(key.open("y2024/d6", &blob).unwrap(), "1 2 3\n");
+        assert!(key.open("y2024/d7", &blob).is_err());
+        assert!(CacheKey::from_hex(&"07".repeat(32))
+            .unwrap()
+            .open("y2024/d6", &blob)
+            .is_err());
+        let mut tampered = blob.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(key.open("y2024/d6", &tampered).is_err());
+        assert!(key.open("y2024/d6", b"1 2 3\n").is_err());
+    }
+
+    #[test]
+    fn test_from_hex() {
+        let hex = "00112233445566778899aabbccddeeff00112233445566778899AABBCCDDEEFF\n";
+        assert!(CacheKey::from_hex(hex).is_ok());
+        assert!(CacheKey::from_hex("a secret shared by the team").is_err());
+        assert!(CacheKey::from_hex(&"ab".repeat(16)).is_err());
+        assert!(CacheKey::from_hex(&"zz".repeat(32)).is_err());
+        assert!(CacheKey::from_hex(&"é".repeat(32)).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use time::OffsetDateTime;
+
+use crate::calendar;
+
+/// Everything that can go wrong talking to Advent of Code or reading local files
+#[derive(Debug)]
+pub enum ClientError {
+    /// The session file couldn't be read
+    MissingSession { path: PathBuf, source: io::Error },
+    /// Advent of Code didn't accept the session cookie, it answered with a 400 or a login page
+    SessionExpired,
+    /// The event has no such day or part
+    NoSuchPuzzle(String),
+    /// The puzzle isn't out yet, Advent of Code answered with a 404
+    NotUnlocked { year: u32, day: u8 },
+    /// Advent of Code answered with another unexpected status
+    Status { url: String, status: u16 },
+    /// The request didn't get a response
+    Network(reqwest::Error),
+    /// The configuration is invalid
+    Config(String),
+    /// A file in the cache couldn't be read or written
+    Cache(String),
+    /// The answer wasn't submitted, with the reason
+    NotSubmitted(String),
+    /// The session cookie couldn't be imported from a browser
+    BrowserCookies(String),
+    /// A local file couldn't be read or written
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession { path, source } => write!(
+                f,
+                "could not read the session file {}: {}. Log in to adventofcode.com and save the \
+                 value of the `session` cookie in it, import it with `advent login` or set \
+                 ADVENT_SESSION",
+                path.display(),
+                source
+            ),
+            ClientError::SessionExpired => write!(
+                f,
+                "your session cookie expired or is invalid. Log in to adventofcode.com again and \
+                 save the new value of the `session` cookie in the session file, or import it \
+                 with `advent login`"
+            ),
+            ClientError::NoSuchPuzzle(reason) => write!(f, "{}", reason),
+            ClientError::NotUnlocked { year, day } => {
+                write!(f, "day {} of {} isn't unlocked yet", day, year)?;
+                match calendar::time_until_unlock(*year, *day, OffsetDateTime::now_utc()) {
+                    Some(wait) => write!(f, ", it unlocks in {}", calendar::format_wait(wait)),
+                    None => Ok(()),
+                }
+            }
+            ClientError::Status { url, status } => {
+                write!(f, "{} answered with status {}", url, status)
+            }
+            ClientError::Network(err) => write!(
+                f,
+                "could not reach Advent of Code, check your connection: {}",
+                err
+            ),
+            ClientError::Config(message) => write!(f, "{}", message),
+            ClientError::Cache(message) => write!(f, "{}", message),
+            ClientError::NotSubmitted(reason) => write!(f, "not submitted, {}", reason),
+            ClientError::BrowserCookies(message) => write!(f, "{}", message),
+            ClientError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::MissingSession { source, .. } => Some(source),
+            ClientError::Network(err) => Some(err),
+            ClientError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        ClientError::Network(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        let err = ClientError::MissingSession {
+            path: PathBuf::from("aoc-client/.session"),
+            source: io::Error::from(io::ErrorKind::NotFound),
+        };
+        assert!(err
+            .to_string()
+            .starts_with("could not read the session file aoc-client/.session: "));
+        assert!(err.source().is_some());
+        assert_eq!(
+            ClientError::NotUnlocked {
+                year: 2024,
+                day: 12
+            }
+            .to_string(),
+            "day 12 of 2024 isn't unlocked yet"
+        );
+        let (year, day) = calendar::next_unlock(OffsetDateTime::now_utc());
+        assert!(ClientError::NotUnlocked { year, day }
+            .to_string()
+            .contains(", it unlocks in "));
+        assert!(ClientError::SessionExpired
+            .to_string()
+            .starts_with("your session cookie expired"));
+    }
+}
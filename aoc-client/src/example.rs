@@ -0,0 +1,207 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use select::document::Document;
+use select::node::Node;
+use select::predicate::Name;
+
+use crate::ClientError;
+
+/// An example input of a puzzle description and the answer it gives
+///
+/// Saved in `examples/yYYYY/` under the cache directory as `dD_P.txt` with the answer in
+/// `dD_P.answer`. Further examples of the same part are numbered, `dD_P_2.txt` and so on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: u8,
+    /// Position among the examples of the part, from 1
+    pub index: usize,
+    pub input: String,
+    pub answer: String,
+}
+
+impl Example {
+    /// Finds the examples of a puzzle page
+    ///
+    /// An example is a `<pre><code>` block after a paragraph mentioning a new example, its
+    /// answer the last emphasised code that follows it in the same part. A part without an
+    /// example of its own reuses the last one of the part before.
+    pub fn extract(html: &str) -> Vec<Example> {
+        let doc = Document::from(html);
+        let mut examples: Vec<Example> = vec![];
+        for (part, article) in (1..).zip(doc.find(Name("article"))) {
+            let previous_input = examples.last().map(|example| example.input.clone());
+            let mut found: Vec<(String, Option<String>)> = vec![];
+            let mut reused: Option<String> = None;
+            let mut paragraph = String::new();
+            for node in article.descendants() {
+                match node.name() {
+                    Some("p") => paragraph = node.text(),
+                    Some("pre") if introduces_example(&paragraph) => {
+                        found.push((node.text(), None));
+                        paragraph.clear();
+                    }
+                    Some("code" | "em") if is_answer(node) => match found.last_mut() {
+                        Some((_, answer)) => *answer = Some(node.text()),
+                        None => reused = Some(node.text()),
+                    },
+                    _ => {}
+                }
+            }
+            if found.is_empty() {
+                found.extend(previous_input.map(|input| (input, reused)));
+            }
+            let part_examples = found
+                .into_iter()
+                .filter_map(|(input, answer)| Some((input, answer?)))
+                .zip(1..)
+                .map(|((input, answer), index)| Example {
+                    part,
+                    index,
+                    input,
+                    answer,
+                });
+            examples.extend(part_examples);
+        }
+        examples
+    }
+
+    /// Loads the saved examples of a day, none if there are none
+    pub fn load(cache_dir: &Path, year: u32, day: u8) -> Result<Vec<Example>, ClientError> {
+        let mut examples = vec![];
+        for part in 1..=2 {
+            for index in 1.. {
+                let path = Self::path(cache_dir, year, day, part, index);
+                let Ok(input) = fs::read_to_string(&path) else {
+                    break;
+                };
+                let answer_path = path.with_extension("answer");
+                let answer = fs::read_to_string(&answer_path).map_err(|err| {
+                    ClientError::Cache(format!("could not read {}: {}", answer_path.display(), err))
+                })?;
+                examples.push(Example {
+                    part,
+                    index,
+                    input,
+                    answer: answer.trim().to_string(),
+                });
+            }
+        }
+        Ok(examples)
+    }
+
+    /// Saves the example, unless it was saved before and may have been corrected by hand
+    pub fn save(&self, cache_dir: &Path, year: u32, day: u8) -> Result<(), ClientError> {
+        let path = Self::path(cache_dir, year, day, self.part, self.index);
+        if path.exists() {
+            return Ok(());
+        }
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, &self.input)?;
+        fs::write(path.with_extension("answer"), format!("{}\n", self.answer))?;
+        Ok(())
+    }
+
+    /// `d6_1` for the first example of part 1 of day 6, `d6_1_2` for the second
+    pub fn name(&self, day: u8) -> String {
+        name(day, self.part, self.index)
+    }
+
+    fn path(cache_dir: &Path, year: u32, day: u8, part: u8, index: usize) -> PathBuf {
+        cache_dir.join(format!("examples/y{}/{}.txt", year, name(day, part, index)))
+    }
+}
+
+fn name(day: u8, part: u8, index: usize) -> String {
+    match index {
+        1 => format!("d{}_{}", day, part),
+        index => format!("d{}_{}_{}", day, part, index),
+    }
+}
+
+/// Part 2 often shows the example of part 1 again, marked up, after "the example above"
+fn introduces_example(paragraph: &str) -> bool {
+    let paragraph = paragraph.to_lowercase();
+    paragraph.contains("example")
+        && !paragraph.contains("example above")
+        && !paragraph.contains("same example")
+}
+
+/// Answers are emphasised code, `<code><em>41</em></code>` or `<em><code>41</code></em>`
+fn is_answer(node: Node) -> bool {
+    match node.name() {
+        Some("code") => node.find(Name("em")).next().is_some(),
+        Some("em") => node.children().all(|child| child.is(Name("code"))),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let examples = Example::extract(include_str!("../fixtures/puzzle/solved.html"));
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    part: 1,
+                    index: 1,
+                    input: "..#.\n#...\n.^..\n".to_string(),
+                    answer: "5".to_string(),
+                },
+                Example {
+                    part: 2,
+                    index: 1,
+                    input: "..#.\n#...\n.^..\n".to_string(),
+                    answer: "2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_own_examples() {
+        let html = "<article><p>For example:</p><pre><code>1abc2\n</code></pre>\
+            <p>Adding these together produces <code><em>12</em></code>.</p>\
+            <p>A larger example:</p><pre><code>a1b2c3d4e5f\n</code></pre>\
+            <p>Here the sum is <em><code>15</code></em>.</p><p><em>What is the sum?</em></p>\
+            </article><article><p>For example:</p><pre><code>two1nine\n</code></pre>\
+            <p>The sum is <code><em>29</em></code>.</p></article>";
+        let examples = Example::extract(html);
+        let found: Vec<_> = examples
+            .iter()
+            .map(|example| {
+                (
+                    example.name(1),
+                    example.input.as_str(),
+                    example.answer.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("d1_1".to_string(), "1abc2\n", "12"),
+                ("d1_1_2".to_string(), "a1b2c3d4e5f\n", "15"),
+                ("d1_2".to_string(), "two1nine\n", "29"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let cache_dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let examples = Example::extract(include_str!("../fixtures/puzzle/solved.html"));
+        for example in &examples {
+            example.save(&cache_dir, 2024, 6).unwrap();
+        }
+        assert!(cache_dir.join("examples/y2024/d6_1.txt").is_file());
+        assert!(cache_dir.join("examples/y2024/d6_2.answer").is_file());
+        assert_eq!(Example::load(&cache_dir, 2024, 6).unwrap(), examples);
+        assert!(Example::load(&cache_dir, 2024, 7).unwrap().is_empty());
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
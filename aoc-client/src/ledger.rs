@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ClientError, Hint, SolutionPart, SubmitOutcome};
+
+/// An answer that was submitted and what Advent of Code said about it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    /// Seconds since the Unix epoch
+    pub submitted_at: u64,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+}
+
+/// Every submission of a part, kept in the cache so wrong answers are never submitted twice
+///
+/// Stored as JSON in `submissions/yYYYY/dD_P.json` under the cache directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ledger {
+    path: PathBuf,
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Loads the submissions of a part, none if it was never submitted
+    pub fn load(cache_dir: &Path, solution_part: &SolutionPart) -> Result<Self, ClientError> {
+        let SolutionPart { year, day, part } = solution_part;
+        let path = cache_dir.join(format!("submissions/y{}/d{}_{}.json", year, day, part));
+        let submissions = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|err| {
+                ClientError::Cache(format!("could not read {}: {}", path.display(), err))
+            })?,
+            Err(_) => vec![],
+        };
+        Ok(Self { path, submissions })
+    }
+
+    /// The answers that are too low and too high, the closest ones learned so far
+    pub fn bounds(&self) -> (Option<i64>, Option<i64>) {
+        let (mut low, mut high) = (None, None);
+        for submission in &self.submissions {
+            let Ok(answer) = submission.answer.parse::<i64>() else {
+                continue;
+            };
+            match submission.outcome {
+                SubmitOutcome::Incorrect { hint: Hint::TooLow } => low = low.max(Some(answer)),
+                SubmitOutcome::Incorrect {
+                    hint: Hint::TooHigh,
+                } => high = Some(high.map_or(answer, |high: i64| high.min(answer))),
+                _ => {}
+            }
+        }
+        (low, high)
+    }
+
+    /// Returns why the answer shouldn't be submitted, if the submissions so far rule it out
+    pub fn check(&self, answer: &str) -> Result<(), String> {
+        for submission in &self.submissions {
+            match &submission.outcome {
+                SubmitOutcome::Correct => {
+                    return Err(format!(
+                        "the part is already solved, the answer was {}",
+                        submission.answer
+                    ))
+                }
+                SubmitOutcome::Incorrect { .. } if submission.answer == answer => {
+                    return Err(format!("{} was already rejected", answer))
+                }
+                _ => {}
+            }
+        }
+        if let Ok(number) = answer.parse::<i64>() {
+            match self.bounds() {
+                (Some(low), _) if number <= low => {
+                    return Err(format!("{} is too low, {} already was", number, low))
+                }
+                (_, Some(high)) if number >= high => {
+                    return Err(format!("{} is too high, {} already was", number, high))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a submission and saves the ledger
+    pub fn record(&mut self, answer: &str, outcome: SubmitOutcome) -> Result<(), ClientError> {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        self.submissions.push(Submission {
+            submitted_at,
+            answer: answer.to_string(),
+            outcome,
+        });
+        fs::create_dir_all(self.path.parent().unwrap())?;
+        let json = serde_json::to_string_pretty(&self.submissions)
+            .map_err(|err| ClientError::Cache(err.to_string()))?;
+        fs::write(&self.path, json)?;
+        Ok(())
+    }
+}
+
+/// When the account may submit again, shared by every process using the same cache
+///
+/// Advent of Code limits submissions per account, not per puzzle, so there is a single
+/// `submissions/cooldown` file holding the end of the cooldown in milliseconds since the Unix
+/// epoch.
+#[derive(Debug, Clone)]
+pub struct Cooldown {
+    path: PathBuf,
+}
+
+impl Cooldown {
+    pub fn new(cache_dir: &Path) -> Self {
+        Self {
+            path: cache_dir.join("submissions/cooldown"),
+        }
+    }
+
+    /// The time left before the next submission, if any
+    pub fn remaining(&self) -> Option<Duration> {
+        let end: u128 = fs::read_to_string(&self.path).ok()?.trim().parse().ok()?;
+        let remaining = end.checked_sub(now_millis())?;
+        (remaining > 0).then(|| Duration::from_millis(remaining as u64))
+    }
+
+    /// Records that nothing may be submitted for the given time
+    pub fn start(&self, wait: Duration) -> Result<(), ClientError> {
+        let end = now_millis() + wait.as_millis();
+        fs::create_dir_all(self.path.parent().unwrap())?;
+        fs::write(&self.path, end.to_string())?;
+        Ok(())
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(submissions: &[(&str, SubmitOutcome)]) -> Ledger {
+        Ledger {
+            path: PathBuf::new(),
+            submissions: submissions
+                .iter()
+                .map(|(answer, outcome)| Submission {
+                    submitted_at: 0,
+                    answer: answer.to_string(),
+                    outcome: outcome.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn incorrect(hint: Hint) -> SubmitOutcome {
+        SubmitOutcome::Incorrect { hint }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let ledger = ledger(&[
+            ("10", incorrect(Hint::TooLow)),
+            ("90", incorrect(Hint::TooHigh)),
+            ("20", incorrect(Hint::TooLow)),
+            ("80", incorrect(Hint::TooHigh)),
+            ("50", incorrect(Hint::None)),
+        ]);
+        assert_eq!(ledger.bounds(), (Some(20), Some(80)));
+        assert!(ledger.check("21").is_ok());
+        assert!(ledger.check("79").is_ok());
+        assert!(ledger.check("20").is_err());
+        assert!(ledger.check("85").is_err());
+        assert!(ledger.check("50").is_err());
+        assert!(ledger.check("abc").is_ok());
+    }
+
+    #[test]
+    fn test_solved() {
+        let ledger = ledger(&[
+            ("abc", incorrect(Hint::None)),
+            ("def", SubmitOutcome::Correct),
+        ]);
+        assert_eq!(ledger.bounds(), (None, None));
+        assert!(ledger.check("abc").is_err());
+        assert!(ledger.check("ghi").is_err());
+    }
+
+    #[test]
+    fn test_record() {
+        let cache_dir = std::env::temp_dir().join(format!("aoc-ledger-{}", std::process::id()));
+        let solution_part = SolutionPart::create(2024, 7, 2);
+        let mut ledger = Ledger::load(&cache_dir, &solution_part).unwrap();
+        assert!(ledger.submissions.is_empty());
+        ledger.record("42", incorrect(Hint::TooLow)).unwrap();
+        let loaded = Ledger::load(&cache_dir, &solution_part).unwrap();
+        assert_eq!(loaded, ledger);
+        assert!(cache_dir.join("submissions/y2024/d7_2.json").is_file());
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let cache_dir = std::env::temp_dir().join(format!("aoc-cooldown-{}", std::process::id()));
+        let cooldown = Cooldown::new(&cache_dir);
+        assert_eq!(cooldown.remaining(), None);
+        cooldown.start(Duration::from_secs(60)).unwrap();
+        let remaining = Cooldown::new(&cache_dir).remaining().unwrap();
+        assert!(remaining > Duration::from_secs(59) && remaining <= Duration::from_secs(60));
+        cooldown.start(Duration::ZERO).unwrap();
+        assert_eq!(cooldown.remaining(), None);
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
@@ -1,5 +1,36 @@
 // Version taken form https://github.com/AlexanderReaper7/advent-of-code-rs/blob/master/aoc-lib/src/lib.rs
+mod adventofcode;
+mod answers;
+mod cache;
+pub mod calendar;
 mod client;
+mod config;
+mod encrypt;
+mod error;
+mod example;
+mod ledger;
+mod local;
+mod provider;
+mod puzzle;
+mod session;
 mod solution;
-pub use client::Client;
-pub use solution::SolutionPart;
+#[cfg(test)]
+mod stub;
+mod submit;
+mod throttle;
+pub use adventofcode::{AdventOfCode, USER_AGENT};
+pub use answers::Answers;
+pub use cache::{Entry, InputCache, Metadata};
+pub use client::{Client, Fetched};
+pub use config::{Config, Profile, SubmitMode, CONFIG_FILE};
+pub use encrypt::CacheKey;
+pub use error::ClientError;
+pub use example::Example;
+pub use ledger::{Cooldown, Ledger, Submission};
+pub use local::LocalPuzzles;
+pub use provider::{PuzzleProvider, Submitted};
+pub use puzzle::Puzzle;
+pub use session::{Session, SESSION_VAR};
+pub use solution::{Answer, SolutionPart};
+pub use submit::{Hint, SubmitOutcome};
+pub use throttle::{Throttle, REQUEST_INTERVAL};
@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::provider::{PuzzleProvider, Submitted};
+use crate::{ClientError, Hint, SolutionPart, SubmitOutcome};
+
+/// Puzzles kept in a local directory, for puzzle sets that aren't on adventofcode.com
+///
+/// Every day of `yYYYY/` has its input in `dD.txt`, its description in `dD.html` and the
+/// answers in `dD_1.answer` and `dD_2.answer`. Answers are checked against those files, with
+/// a hint when both are numbers. The days are the inputs there are, any number of them, and
+/// they are all out.
+pub struct LocalPuzzles {
+    root: PathBuf,
+}
+
+impl LocalPuzzles {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn read(&self, year: u32, day: u8, file: &str) -> Result<String, ClientError> {
+        let path = self.root.join(format!("y{}/{}", year, file));
+        fs::read_to_string(&path).map_err(|err| missing(&path, year, day, err))
+    }
+}
+
+impl PuzzleProvider for LocalPuzzles {
+    fn fetch_input(&self, year: u32, day: u8) -> Result<String, ClientError> {
+        self.read(year, day, &format!("d{}.txt", day))
+    }
+
+    fn submit(&self, solution_part: &SolutionPart, answer: &str) -> Result<Submitted, ClientError> {
+        let SolutionPart { year, day, part } = *solution_part;
+        let expected = self.read(year, day, &format!("d{}_{}.answer", day, part))?;
+        let expected = expected.trim();
+        let outcome = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+            _ if answer == expected => SubmitOutcome::Correct,
+            (Ok(answer), Ok(expected)) if answer > expected => SubmitOutcome::Incorrect {
+                hint: Hint::TooHigh,
+            },
+            (Ok(_), Ok(_)) => SubmitOutcome::Incorrect { hint: Hint::TooLow },
+            _ => SubmitOutcome::Incorrect { hint: Hint::None },
+        };
+        Ok(Submitted {
+            outcome,
+            cooldown: None,
+        })
+    }
+
+    fn fetch_description(&self, year: u32, day: u8) -> Result<String, ClientError> {
+        self.read(year, day, &format!("d{}.html", day))
+    }
+
+    fn days(&self, year: u32) -> Result<Vec<u8>, ClientError> {
+        let dir = self.root.join(format!("y{}", year));
+        let entries = fs::read_dir(&dir).map_err(|err| {
+            ClientError::NoSuchPuzzle(format!("no puzzles in {}: {}", dir.display(), err))
+        })?;
+        let mut days = vec![];
+        for entry in entries {
+            let name = entry?.file_name();
+            let day = name
+                .to_str()
+                .and_then(|name| name.strip_prefix('d'))
+                .and_then(|name| name.strip_suffix(".txt"))
+                .and_then(|day| day.parse::<u8>().ok());
+            days.extend(day);
+        }
+        days.sort();
+        Ok(days)
+    }
+
+    fn account(&self) -> String {
+        format!("local {}", self.root.display())
+    }
+}
+
+fn missing(path: &Path, year: u32, day: u8, err: std::io::Error) -> ClientError {
+    ClientError::NoSuchPuzzle(format!(
+        "no puzzle file {} for day {} of {}: {}",
+        path.display(),
+        day,
+        year,
+        err
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_puzzles() {
+        let root = std::env::temp_dir().join(format!("aoc-local-{}", std::process::id()));
+        fs::create_dir_all(root.join("y2024")).unwrap();
+        fs::write(root.join("y2024/d3.txt"), "1 2 3\n").unwrap();
+        fs::write(root.join("y2024/d3_1.answer"), "6\n").unwrap();
+        fs::write(root.join("y2024/d40.txt"), "4\n").unwrap();
+        let provider = LocalPuzzles::new(&root);
+        assert_eq!(provider.fetch_input(2024, 3).unwrap(), "1 2 3\n");
+        assert_eq!(provider.days(2024).unwrap(), vec![3, 40]);
+        assert_eq!(provider.time_until_unlock(2024, 40), None);
+        assert!(provider.days(2023).is_err());
+        let submit = |answer| {
+            provider
+                .submit(&SolutionPart::create(2024, 3, 1), answer)
+                .unwrap()
+                .outcome
+        };
+        assert_eq!(submit("6"), SubmitOutcome::Correct);
+        assert_eq!(
+            submit("7"),
+            SubmitOutcome::Incorrect {
+                hint: Hint::TooHigh
+            }
+        );
+        assert_eq!(submit("5"), SubmitOutcome::Incorrect { hint: Hint::TooLow });
+        assert!(matches!(
+            provider.fetch_description(2024, 3),
+            Err(ClientError::NoSuchPuzzle(_))
+        ));
+        assert!(provider
+            .submit(&SolutionPart::create(2024, 3, 2), "1")
+            .is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use crate::{ClientError, SolutionPart, SubmitOutcome};
+
+/// What a provider answered to a submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submitted {
+    pub outcome: SubmitOutcome,
+    /// How long to wait before submitting anything again
+    pub cooldown: Option<Duration>,
+}
+
+/// A source of puzzles, its inputs, descriptions and the judge of its answers
+///
+/// The [`Client`](crate::Client) caches what a provider returns and keeps track of the
+/// submissions, so a provider only talks to its source.
+pub trait PuzzleProvider: Send + Sync {
+    /// Gets the input of the day
+    fn fetch_input(&self, year: u32, day: u8) -> Result<String, ClientError>;
+
+    /// Sends the answer of a part
+    fn submit(&self, solution_part: &SolutionPart, answer: &str) -> Result<Submitted, ClientError>;
+
+    /// Gets the page describing the puzzle of the day, as HTML
+    fn fetch_description(&self, year: u32, day: u8) -> Result<String, ClientError>;
+
+    /// The days of the year that have a puzzle, in order, those not out yet included
+    fn days(&self, year: u32) -> Result<Vec<u8>, ClientError>;
+
+    /// Time left before the puzzle of the day is out, `None` once it is
+    fn time_until_unlock(&self, _year: u32, _day: u8) -> Option<Duration> {
+        None
+    }
+
+    /// Identifies the account inputs are fetched for, inputs differ by account
+    ///
+    /// Kept in the cache metadata, so it must not reveal any secret.
+    fn account(&self) -> String;
+}
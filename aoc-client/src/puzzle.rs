@@ -0,0 +1,246 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name};
+
+use crate::Example;
+
+/// The page of a day, with its description and, once part 1 is solved, part 2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u8,
+    html: String,
+}
+
+/// How descriptions are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Markdown,
+    /// Plain text with ANSI escape codes for emphasis
+    Terminal,
+}
+
+impl Puzzle {
+    pub fn new(year: u32, day: u8, html: String) -> Self {
+        Self { year, day, html }
+    }
+
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    /// Number of parts the page describes
+    pub fn parts(&self) -> usize {
+        Document::from(self.html.as_str())
+            .find(Name("article"))
+            .count()
+    }
+
+    /// Whether both parts are solved, the page won't change anymore
+    pub fn is_complete(&self) -> bool {
+        Document::from(self.html.as_str())
+            .find(Class("day-success"))
+            .next()
+            .is_some()
+    }
+
+    /// The answers accepted so far, in part order
+    pub fn answers(&self) -> Vec<String> {
+        Document::from(self.html.as_str())
+            .find(Name("p"))
+            .filter(|p| p.text().starts_with("Your puzzle answer was"))
+            .filter_map(|p| p.find(Name("code")).next())
+            .map(|code| code.text())
+            .collect()
+    }
+
+    /// The example inputs of the description and their answers
+    pub fn examples(&self) -> Vec<Example> {
+        Example::extract(&self.html)
+    }
+
+    /// The description as Markdown
+    pub fn to_markdown(&self) -> String {
+        self.render(Style::Markdown)
+    }
+
+    /// The description as text for a terminal, emphasis and code highlighted
+    pub fn to_terminal(&self) -> String {
+        self.render(Style::Terminal)
+    }
+
+    fn render(&self, style: Style) -> String {
+        let doc = Document::from(self.html.as_str());
+        let articles: Vec<String> = doc
+            .find(Name("article"))
+            .map(|article| {
+                let mut text = String::new();
+                render_children(article, style, &mut text);
+                text.trim().to_string()
+            })
+            .collect();
+        let mut text = articles.join("\n\n");
+        text.push('\n');
+        text
+    }
+}
+
+fn render_children(node: Node, style: Style, out: &mut String) {
+    for child in node.children() {
+        render_node(child, style, out);
+    }
+}
+
+fn render_node(node: Node, style: Style, out: &mut String) {
+    let Some(name) = node.name() else {
+        // line breaks between blocks are left to the blocks
+        let between_blocks = node
+            .parent()
+            .and_then(|parent| parent.name())
+            .is_some_and(|parent| parent == "article" || parent == "ul");
+        match node.as_text() {
+            Some(text) if between_blocks && text.trim().is_empty() => {}
+            Some(text) => out.push_str(text),
+            None => {}
+        }
+        return;
+    };
+    match (name, style) {
+        ("h2", Style::Markdown) => {
+            out.push_str(&format!("## {}\n\n", title(&node.text())));
+        }
+        ("h2", Style::Terminal) => {
+            out.push_str(&format!("\x1b[1m{}\x1b[0m\n\n", node.text()));
+        }
+        ("p", _) => {
+            render_children(node, style, out);
+            out.push_str("\n\n");
+        }
+        ("ul", _) => {
+            render_children(node, style, out);
+            out.push('\n');
+        }
+        ("li", _) => {
+            out.push_str("- ");
+            render_children(node, style, out);
+            out.push('\n');
+        }
+        ("pre", Style::Markdown) => {
+            out.push_str(&format!("```\n{}\n```\n\n", node.text().trim_end()));
+        }
+        ("pre", Style::Terminal) => {
+            for line in node.text().trim_end().lines() {
+                out.push_str(&format!("    {}\n", line));
+            }
+            out.push('\n');
+        }
+        // answers are often emphasised code, the emphasis goes around it
+        ("code", Style::Markdown) if node.find(Name("em")).next().is_some() => {
+            out.push_str(&format!("**`{}`**", node.text()));
+        }
+        ("code", Style::Markdown) => out.push_str(&format!("`{}`", node.text())),
+        ("code", Style::Terminal) if node.find(Name("em")).next().is_some() => {
+            out.push_str(&format!("\x1b[1;36m{}\x1b[0m", node.text()));
+        }
+        ("code", Style::Terminal) => out.push_str(&format!("\x1b[36m{}\x1b[0m", node.text())),
+        ("em", Style::Markdown) => {
+            out.push_str("**");
+            render_children(node, style, out);
+            out.push_str("**");
+        }
+        ("em", Style::Terminal) => {
+            out.push_str("\x1b[1m");
+            render_children(node, style, out);
+            out.push_str("\x1b[0m");
+        }
+        ("a", Style::Markdown) => {
+            out.push('[');
+            render_children(node, style, out);
+            let href = node.attr("href").unwrap_or_default();
+            if href.starts_with('/') {
+                out.push_str(&format!("](https://adventofcode.com{})", href));
+            } else {
+                out.push_str(&format!("]({})", href));
+            }
+        }
+        _ => render_children(node, style, out),
+    }
+}
+
+/// "--- Day 6: Guard Gallivant ---" without the dashes
+fn title(heading: &str) -> &str {
+    heading.trim().trim_matches('-').trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle(html: &str) -> Puzzle {
+        Puzzle::new(2024, 6, html.to_string())
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(
+            puzzle(include_str!("../fixtures/puzzle/part1.html")).parts(),
+            1
+        );
+        assert_eq!(
+            puzzle(include_str!("../fixtures/puzzle/solved.html")).parts(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_answers() {
+        let solved = puzzle(include_str!("../fixtures/puzzle/solved.html"));
+        assert_eq!(solved.answers(), vec!["4819", "1796"]);
+        assert!(solved.is_complete());
+        let unsolved = puzzle(include_str!("../fixtures/puzzle/part1.html"));
+        assert!(unsolved.answers().is_empty());
+        assert!(!unsolved.is_complete());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let markdown = puzzle(include_str!("../fixtures/puzzle/part1.html")).to_markdown();
+        let expected = "## Day 6: Sample Stroll
+
+A guard walks through a small lab. Each step it moves **forward** unless something is in the way, then it turns right.
+
+For example:
+
+```
+..#.
+#...
+.^..
+```
+
+In this example, the guard visits **`5`** distinct positions. See [yesterday](https://adventofcode.com/2024/day/5) for the map format.
+
+- Obstacles are `#`.
+- The guard starts at `^`.
+
+Predict the path of the guard. **How many distinct positions will the guard visit before leaving the mapped area?**
+";
+        assert_eq!(markdown, expected);
+    }
+
+    #[test]
+    fn test_part2_is_rendered() {
+        let markdown = puzzle(include_str!("../fixtures/puzzle/solved.html")).to_markdown();
+        assert!(markdown.contains("\n\n## Part Two\n\n"));
+        assert!(markdown.contains("traps the guard in a loop."));
+        assert!(!markdown.contains("Your puzzle answer was"));
+    }
+
+    #[test]
+    fn test_to_terminal() {
+        let text = puzzle(include_str!("../fixtures/puzzle/part1.html")).to_terminal();
+        assert!(text.starts_with("\x1b[1m--- Day 6: Sample Stroll ---\x1b[0m\n\n"));
+        assert!(text.contains("moves \x1b[1mforward\x1b[0m unless"));
+        assert!(text.contains("\n    ..#.\n    #...\n"));
+        assert!(text.contains("See yesterday for"));
+    }
+}
@@ -0,0 +1,281 @@
+//! The adventofcode.com session cookie, where it comes from and who it belongs to
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use select::document::Document;
+use select::predicate::{Class, Name, Predicate};
+use sha2::{Digest, Sha256};
+
+use crate::ClientError;
+
+/// Environment variable the session token is read from before the session file
+pub const SESSION_VAR: &str = "ADVENT_SESSION";
+/// How long a session lasts, Advent of Code doesn't tell so this is an estimate
+const SESSION_LIFETIME: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// Seconds between 1601, where Chromium times start, and 1970
+const CHROMIUM_EPOCH_OFFSET: u64 = 11_644_473_600;
+
+/// Files SQLite keeps beside a database, the latest changes of a running browser are in them
+const SIDECARS: [&str; 3] = ["-wal", "-shm", "-journal"];
+
+/// Copies of cookie databases made so far, to give each one its own name
+static COPIES: AtomicUsize = AtomicUsize::new(0);
+
+/// A session token, with the time it expires at in seconds since the epoch if known
+#[derive(Clone, PartialEq, Eq)]
+pub struct Session {
+    pub token: String,
+    pub expires: Option<u64>,
+}
+
+impl Session {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.trim().to_string(),
+            expires: None,
+        }
+    }
+
+    /// Reads the session file, guessing the expiry from when it was written
+    pub fn load(path: &Path) -> Result<Self, ClientError> {
+        let missing = |source| ClientError::MissingSession {
+            path: path.to_path_buf(),
+            source,
+        };
+        let token = fs::read_to_string(path).map_err(missing)?;
+        let written = fs::metadata(path).and_then(|metadata| metadata.modified());
+        Ok(Self {
+            expires: written
+                .ok()
+                .map(|written| seconds(written + SESSION_LIFETIME)),
+            ..Self::new(&token)
+        })
+    }
+
+    /// Imports the session cookie of adventofcode.com from a Firefox `cookies.sqlite`
+    pub fn from_firefox(path: &Path) -> Result<Self, ClientError> {
+        let row = query_cookies(
+            path,
+            "SELECT value, expiry FROM moz_cookies
+             WHERE name = 'session' AND (host = 'adventofcode.com' OR host = '.adventofcode.com')
+             ORDER BY expiry DESC",
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+        )?;
+        let (token, expiry) = row.ok_or_else(|| not_found(path))?;
+        // newer versions of Firefox keep milliseconds
+        let expiry = match expiry as u64 {
+            expiry if expiry > 100_000_000_000 => expiry / 1000,
+            expiry => expiry,
+        };
+        Ok(Self {
+            expires: (expiry > 0).then_some(expiry),
+            ..Self::new(&token)
+        })
+    }
+
+    /// Imports the session cookie of adventofcode.com from the `Cookies` file of Chromium
+    ///
+    /// On Linux Chromium encrypts cookies with a fixed key unless it uses the keyring of the
+    /// desktop, and cookies encrypted with a key from the keyring can't be imported.
+    pub fn from_chromium(path: &Path) -> Result<Self, ClientError> {
+        let row = query_cookies(
+            path,
+            "SELECT host_key, value, encrypted_value, expires_utc FROM cookies
+             WHERE name = 'session' AND host_key IN ('adventofcode.com', '.adventofcode.com')
+             ORDER BY expires_utc DESC",
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Vec<u8>>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            },
+        )?;
+        let (host, value, encrypted, expires) = row.ok_or_else(|| not_found(path))?;
+        let token = match value.is_empty() {
+            true => decrypt_chromium(&host, &encrypted)?,
+            false => value,
+        };
+        let expires = (expires as u64 / 1_000_000).checked_sub(CHROMIUM_EPOCH_OFFSET);
+        Ok(Self {
+            expires: expires.filter(|expires| *expires > 0),
+            ..Self::new(&token)
+        })
+    }
+
+    /// Writes the token to the session file, only readable by its owner
+    pub fn save(&self, path: &Path) -> Result<(), ClientError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, format!("{}\n", self.token))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Session {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session")
+            .field("token", &"..")
+            .field("expires", &self.expires)
+            .finish()
+    }
+}
+
+/// The name of the user a page was served to, from its header
+pub(crate) fn user_name(html: &str) -> Option<String> {
+    let document = Document::from(html);
+    let user = document
+        .find(Name("header").descendant(Class("user")))
+        .next()?;
+    // the star count is in a span after the name
+    let name: String = user
+        .children()
+        .filter(|child| child.name().is_none())
+        .map(|child| child.text())
+        .collect();
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+/// Runs the query on a copy of the database, browsers keep theirs locked while running
+fn query_cookies<T>(
+    path: &Path,
+    query: &str,
+    row: impl FnOnce(&rusqlite::Row) -> rusqlite::Result<T>,
+) -> Result<Option<T>, ClientError> {
+    let error = |err: &dyn std::fmt::Display| {
+        ClientError::BrowserCookies(format!("could not read {}: {}", path.display(), err))
+    };
+    let copy = std::env::temp_dir().join(format!(
+        "advent-cookies-{}-{}.sqlite",
+        std::process::id(),
+        COPIES.fetch_add(1, Ordering::Relaxed)
+    ));
+    // the copy is ours, so SQLite may write to it to apply the log or roll back the journal
+    let result = copy_database(path, &copy)
+        .map_err(|err| error(&err))
+        .and_then(|()| {
+            Connection::open_with_flags(&copy, OpenFlags::SQLITE_OPEN_READ_WRITE)
+                .and_then(|connection| connection.query_row(query, [], row).optional())
+                .map_err(|err| error(&err))
+        });
+    for suffix in std::iter::once("").chain(SIDECARS) {
+        let _ = fs::remove_file(sidecar(&copy, suffix));
+    }
+    result
+}
+
+/// Copies the database with the files SQLite keeps beside it
+fn copy_database(path: &Path, copy: &Path) -> std::io::Result<()> {
+    fs::copy(path, copy)?;
+    for suffix in SIDECARS {
+        let from = sidecar(path, suffix);
+        if from.exists() {
+            fs::copy(from, sidecar(copy, suffix))?;
+        }
+    }
+    Ok(())
+}
+
+/// The path of the database with the suffix, like `cookies.sqlite-wal`
+fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Decrypts a cookie Chromium encrypted with its fixed Linux key
+fn decrypt_chromium(host: &str, encrypted: &[u8]) -> Result<String, ClientError> {
+    let error = |reason: &str| ClientError::BrowserCookies(format!("the cookie {}", reason));
+    let Some(ciphertext) = encrypted.strip_prefix(b"v10") else {
+        return Err(error(
+            "is encrypted with a key from the desktop keyring, copy it from the browser instead",
+        ));
+    };
+    let mut key = [0; 16];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(b"peanuts", b"saltysalt", 1, &mut key);
+    let mut buffer = ciphertext.to_vec();
+    let plain = cbc::Decryptor::<aes::Aes128>::new(&key.into(), &[b' '; 16].into())
+        .decrypt_padded_mut::<Pkcs7>(&mut buffer)
+        .map_err(|_| error("can't be decrypted"))?;
+    // recent versions start the value with the hash of the host
+    let hash = Sha256::digest(host.as_bytes());
+    let plain = plain.strip_prefix(hash.as_slice()).unwrap_or(plain);
+    String::from_utf8(plain.to_vec()).map_err(|_| error("isn't text once decrypted"))
+}
+
+fn not_found(path: &Path) -> ClientError {
+    ClientError::BrowserCookies(format!(
+        "no adventofcode.com session in {}, log in with that browser first",
+        path.display()
+    ))
+}
+
+fn seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures/cookies")
+            .join(name)
+    }
+
+    #[test]
+    fn test_from_firefox() {
+        let session = Session::from_firefox(&fixture("firefox.sqlite")).unwrap();
+        assert_eq!(
+            session.token,
+            "53616c7465645f5f66697265666f785f73657373696f6e"
+        );
+        assert_eq!(session.expires, Some(1_767_225_600));
+        // the newest cookie of a running browser is only in the write-ahead log
+        let session = Session::from_firefox(&fixture("firefox-wal.sqlite")).unwrap();
+        assert_eq!(session.token, "53616c7465645f5f6e65775f73657373696f6e");
+        assert_eq!(session.expires, Some(1_798_761_600));
+        assert!(matches!(
+            Session::from_firefox(&fixture("chromium.sqlite")),
+            Err(ClientError::BrowserCookies(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_chromium() {
+        let session = Session::from_chromium(&fixture("chromium.sqlite")).unwrap();
+        assert_eq!(
+            session.token,
+            "53616c7465645f5f6368726f6d69756d5f73657373696f6e"
+        );
+        assert_eq!(session.expires, Some(1_767_225_600));
+        let err = decrypt_chromium(".adventofcode.com", b"v11secret").unwrap_err();
+        assert!(err.to_string().contains("keyring"));
+        assert!(Session::from_chromium(&fixture("missing.sqlite")).is_err());
+    }
+
+    #[test]
+    fn test_user_name() {
+        let html = "<html><header><div><h1 class=\"title-global\">Advent of Code</h1>\
+            <div class=\"user\">Nikos Kostoulas <span class=\"star-count\">50*</span></div>\
+            </div></header><main></main></html>";
+        assert_eq!(user_name(html).as_deref(), Some("Nikos Kostoulas"));
+        assert_eq!(user_name("<html><header></header></html>"), None);
+    }
+}
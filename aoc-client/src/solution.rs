@@ -1,3 +1,6 @@
+use crate::calendar;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SolutionPart {
     pub year: u32,
     pub day: u8,
@@ -8,4 +11,172 @@ impl SolutionPart {
     pub fn create(year: u32, day: u8, part: u8) -> Self {
         Self { year, day, part }
     }
+
+    /// Checks that the event has this day and part
+    pub fn validate(&self) -> Result<(), String> {
+        let SolutionPart { year, day, part } = *self;
+        let days = calendar::days_in_year(year).ok_or_else(|| {
+            format!(
+                "there is no event in {}, the first one was in {}",
+                year,
+                calendar::FIRST_YEAR
+            )
+        })?;
+        if day == 0 || day > days {
+            return Err(format!(
+                "the {} event has days 1 to {}, not {}",
+                year, days, day
+            ));
+        }
+        if part == 0 || part > 2 {
+            return Err(format!("puzzles have parts 1 and 2, not {}", part));
+        }
+        Ok(())
+    }
+}
+
+/// The answer of a solution part
+///
+/// Solutions return anything that converts into an `Answer`, so the runner can tell numbers from
+/// text and from letters drawn as ASCII art, and check an answer before it is submitted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    /// Letters drawn on a grid of characters, one row per line
+    Glyphs(String),
+}
+
+impl Answer {
+    /// Returns the text to submit, or why the answer can't be submitted as it is
+    pub fn submission(&self) -> Result<String, String> {
+        match self {
+            Answer::Integer(number) if *number < 0 => {
+                Err(format!("{} is negative, answers never are", number))
+            }
+            Answer::Integer(number) => Ok(number.to_string()),
+            Answer::Text(text) if text.trim().is_empty() => Err("the answer is empty".into()),
+            Answer::Text(text) if text.trim().contains(char::is_whitespace) => {
+                Err(format!("{:?} contains whitespace", text))
+            }
+            Answer::Text(text) => Ok(text.trim().to_string()),
+            Answer::Glyphs(_) => Err(
+                "the answer is drawn as glyphs, read the letters and submit them as text".into(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Glyphs(grid) => write!(f, "\n{}", grid),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Integer(number) => number.to_string() == *other,
+            Answer::Text(text) | Answer::Glyphs(text) => text == other,
+        }
+    }
+}
+
+impl From<String> for Answer {
+    /// Multi-line strings are glyphs, anything else is text
+    fn from(text: String) -> Self {
+        if text.trim_end().contains('\n') {
+            Answer::Glyphs(text)
+        } else {
+            Answer::Text(text)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(number: $t) -> Self {
+                Answer::Integer(number as i64)
+            }
+        })*
+    };
+}
+
+answer_from_integer!(i32, u32, i64);
+
+macro_rules! answer_from_wide_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            /// Numbers that don't fit an `i64` are kept as text
+            fn from(number: $t) -> Self {
+                match i64::try_from(number) {
+                    Ok(number) => Answer::Integer(number),
+                    Err(_) => Answer::Text(number.to_string()),
+                }
+            }
+        })*
+    };
+}
+
+answer_from_wide_integer!(u64, usize, i128, u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(SolutionPart::create(2024, 25, 2).validate().is_ok());
+        assert!(SolutionPart::create(2025, 13, 1).validate().is_err());
+        assert!(SolutionPart::create(2014, 1, 1).validate().is_err());
+        assert!(SolutionPart::create(2024, 1, 3).validate().is_err());
+    }
+
+    #[test]
+    fn test_from_numbers() {
+        assert_eq!(Answer::from(41usize), Answer::Integer(41));
+        assert_eq!(Answer::from(-3i32), Answer::Integer(-3));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::Text("18446744073709551615".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".to_string()));
+        assert_eq!(Answer::from("abc\n"), Answer::Text("abc\n".to_string()));
+        assert_eq!(
+            Answer::from("#..#\n####\n".to_string()),
+            Answer::Glyphs("#..#\n####\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_compare_with_str() {
+        assert_eq!(Answer::Integer(41), "41");
+        assert_eq!(Answer::Text("abc".to_string()), "abc");
+        assert_ne!(Answer::Integer(41), "42");
+    }
+
+    #[test]
+    fn test_submission() {
+        assert_eq!(Answer::Integer(41).submission(), Ok("41".to_string()));
+        assert_eq!(Answer::from(" ab\n").submission(), Ok("ab".to_string()));
+        assert!(Answer::Integer(-1).submission().is_err());
+        assert!(Answer::from("").submission().is_err());
+        assert!(Answer::from("a b").submission().is_err());
+        assert!(Answer::from("#.\n.#").submission().is_err());
+    }
 }
@@ -0,0 +1,85 @@
+//! A local HTTP server that plays Advent of Code in tests
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A request the stub received, the request line, the cookie and user agent sent and the body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Request {
+    pub line: String,
+    pub cookie: Option<String>,
+    pub user_agent: Option<String>,
+    pub body: String,
+}
+
+/// Answers one request per response given, in order, then stops listening
+pub(crate) struct StubServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl StubServer {
+    pub fn start(responses: Vec<(u16, String)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let received = requests.clone();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let request = read_request(&mut reader);
+                received.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = reader.get_mut().write_all(response.as_bytes());
+            }
+        });
+        Self { url, requests }
+    }
+
+    /// The requests received so far
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> Request {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let mut length = 0;
+    let mut cookie = None;
+    let mut user_agent = None;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().unwrap();
+            } else if name.eq_ignore_ascii_case("cookie") {
+                cookie = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("user-agent") {
+                user_agent = Some(value.trim().to_string());
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    Request {
+        line: line.trim().to_string(),
+        cookie,
+        user_agent,
+        body: String::from_utf8_lossy(&body).to_string(),
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+use std::time::Duration;
+
+use select::document::Document;
+use select::predicate::Name;
+use serde::{Deserialize, Serialize};
+
+/// Whether a wrong answer was too high or too low, when Advent of Code says so
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+    None,
+}
+
+/// What Advent of Code said about a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect {
+        hint: Hint,
+    },
+    /// An answer was submitted too recently, another one is accepted after `wait`
+    RateLimited {
+        wait: Duration,
+    },
+    /// The part was already solved, its answer isn't checked again
+    AlreadySolved,
+    /// The part can't be submitted yet, usually because the previous part isn't solved
+    WrongLevel,
+    /// The text of a response that wasn't recognised
+    Unknown(String),
+}
+
+impl SubmitOutcome {
+    /// Parses the page returned after submitting an answer
+    pub fn parse(html: &str) -> Self {
+        let text = main_text(html);
+        if text.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = if text.contains("your answer is too high") {
+                Hint::TooHigh
+            } else if text.contains("your answer is too low") {
+                Hint::TooLow
+            } else {
+                Hint::None
+            };
+            SubmitOutcome::Incorrect { hint }
+        } else if text.contains("You gave an answer too recently") {
+            match parse_wait(&text) {
+                Some(wait) => SubmitOutcome::RateLimited { wait },
+                None => SubmitOutcome::Unknown(text),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::WrongLevel
+        } else {
+            SubmitOutcome::Unknown(text)
+        }
+    }
+
+    pub fn is_correct(&self) -> bool {
+        matches!(self, SubmitOutcome::Correct)
+    }
+}
+
+impl fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::Incorrect {
+                hint: Hint::TooHigh,
+            } => {
+                write!(f, "That's not the right answer, it is too high.")
+            }
+            SubmitOutcome::Incorrect { hint: Hint::TooLow } => {
+                write!(f, "That's not the right answer, it is too low.")
+            }
+            SubmitOutcome::Incorrect { hint: Hint::None } => {
+                write!(f, "That's not the right answer.")
+            }
+            SubmitOutcome::RateLimited { wait } => write!(
+                f,
+                "An answer was submitted too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            SubmitOutcome::AlreadySolved => write!(f, "This part is already solved."),
+            SubmitOutcome::WrongLevel => {
+                write!(
+                    f,
+                    "This part can't be submitted, is the previous one solved?"
+                )
+            }
+            SubmitOutcome::Unknown(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// How long Advent of Code won't accept another answer after this response
+///
+/// Besides rate limited submissions, wrong answers start a cooldown of one minute or more.
+pub(crate) fn cooldown(html: &str) -> Option<Duration> {
+    let text = main_text(html);
+    if let Some(wait) = parse_wait(&text) {
+        return Some(wait);
+    }
+    let (_, rest) = text.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        number => number.parse().ok()?,
+    };
+    words
+        .next()?
+        .starts_with("minute")
+        .then(|| Duration::from_secs(minutes * 60))
+}
+
+/// Number of solved parts on the page of a day
+pub(crate) fn solved_parts(html: &str) -> usize {
+    main_text(html).matches("Your puzzle answer was").count()
+}
+
+/// The text of the `<main>` element, with whitespace collapsed
+fn main_text(html: &str) -> String {
+    let doc = Document::from(html);
+    let text = match doc.find(Name("main")).next() {
+        Some(node) => node.text(),
+        None => doc
+            .find(Name("body"))
+            .next()
+            .map(|node| node.text())
+            .unwrap_or_default(),
+    };
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses the wait from "You have 4m 31s left to wait"
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    let mut seconds = 0;
+    for amount in wait.split_whitespace() {
+        let unit_at = amount.find(|c: char| !c.is_ascii_digit())?;
+        let (number, unit) = amount.split_at(unit_at);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_correct() {
+        let outcome = SubmitOutcome::parse(include_str!("../fixtures/submit/correct.html"));
+        assert_eq!(outcome, SubmitOutcome::Correct);
+        assert!(outcome.is_correct());
+    }
+
+    #[test]
+    fn test_parse_incorrect() {
+        let parse = |html| match SubmitOutcome::parse(html) {
+            SubmitOutcome::Incorrect { hint } => hint,
+            outcome => panic!("expected an incorrect answer, got {:?}", outcome),
+        };
+        assert_eq!(
+            parse(include_str!("../fixtures/submit/too_high.html")),
+            Hint::TooHigh
+        );
+        assert_eq!(
+            parse(include_str!("../fixtures/submit/too_low.html")),
+            Hint::TooLow
+        );
+        assert_eq!(
+            parse(include_str!("../fixtures/submit/incorrect.html")),
+            Hint::None
+        );
+    }
+
+    #[test]
+    fn test_parse_rate_limited() {
+        assert_eq!(
+            SubmitOutcome::parse(include_str!("../fixtures/submit/rate_limited.html")),
+            SubmitOutcome::RateLimited {
+                wait: Duration::from_secs(271)
+            }
+        );
+        assert_eq!(
+            parse_wait("You have 1h 2s left to wait."),
+            Some(Duration::from_secs(3602))
+        );
+        assert_eq!(parse_wait("You have a while left to wait."), None);
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let minute = Some(Duration::from_secs(60));
+        assert_eq!(
+            cooldown(include_str!("../fixtures/submit/too_low.html")),
+            minute
+        );
+        assert_eq!(
+            cooldown(include_str!("../fixtures/submit/rate_limited.html")),
+            Some(Duration::from_secs(271))
+        );
+        assert_eq!(
+            cooldown("<main>Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.</main>"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            cooldown(include_str!("../fixtures/submit/correct.html")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_wrong_level() {
+        assert_eq!(
+            SubmitOutcome::parse(include_str!("../fixtures/submit/wrong_level.html")),
+            SubmitOutcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown() {
+        assert_eq!(
+            SubmitOutcome::parse("<main><p>Something   new.</p></main>"),
+            SubmitOutcome::Unknown("Something new.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_solved_parts() {
+        assert_eq!(
+            solved_parts(include_str!("../fixtures/submit/solved_day.html")),
+            2
+        );
+        assert_eq!(
+            solved_parts(include_str!("../fixtures/submit/correct.html")),
+            0
+        );
+    }
+}
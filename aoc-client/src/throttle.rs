@@ -0,0 +1,89 @@
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::ClientError;
+
+/// Time between two requests to adventofcode.com, it asks tools to keep their traffic low
+pub const REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Keeps requests an interval apart, across every process sharing the lock file
+///
+/// The file holds the time of the last request. A process waits for its turn while holding
+/// the lock on it, so processes queue up instead of sending their requests together.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl Throttle {
+    pub fn new(path: impl Into<PathBuf>, interval: Duration) -> Self {
+        Self {
+            path: path.into(),
+            interval,
+        }
+    }
+
+    /// Waits until a request may be sent and records it as sent
+    pub fn wait(&self) -> Result<(), ClientError> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        // released when the file is closed
+        file.lock()?;
+        let mut last = String::new();
+        file.read_to_string(&mut last)?;
+        if let Ok(last) = last.trim().parse::<u64>() {
+            let next = UNIX_EPOCH + Duration::from_nanos(last) + self.interval;
+            if let Ok(wait) = next.duration_since(SystemTime::now()) {
+                // a clock set back doesn't make anyone wait for longer than the interval
+                thread::sleep(wait.min(self.interval));
+            }
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        file.set_len(0)?;
+        file.rewind()?;
+        write!(file, "{}", now)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_requests_are_spaced() {
+        let dir = std::env::temp_dir().join(format!("aoc-throttle-{}", std::process::id()));
+        let interval = Duration::from_millis(200);
+        let throttle = Throttle::new(dir.join("throttle.lock"), interval);
+        let start = Instant::now();
+        throttle.wait().unwrap();
+        assert!(start.elapsed() < interval);
+        // other processes open the file on their own, so do threads with their own throttle
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let throttle = Throttle::new(dir.join("throttle.lock"), interval);
+                thread::spawn(move || throttle.wait().unwrap())
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(start.elapsed() >= interval * 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -54,9 +54,9 @@ impl Parser {
     }
 
     pub fn set(&mut self, target: &char) {
-        self.characters
-            .get_mut(self.cursor)
-            .map(|ref mut c| (**c) = *target);
+        if let Some(c) = self.characters.get_mut(self.cursor) {
+            *c = *target;
+        }
     }
 
     pub fn pop(&mut self) -> Option<&char> {
@@ -89,8 +89,8 @@ impl Parser {
                     .skip(1)
                     .all(|(i, t)| self.peek_at((j + i) as i32) == Some(&t))
         });
-        if position.is_some() {
-            self.cursor += position.unwrap() + target.len() - 1;
+        if let Some(position) = position {
+            self.cursor += position + target.len() - 1;
             true
         } else {
             self.cursor = self.characters.len();
@@ -1,17 +1,36 @@
 //! Auto-generated file by build script, do not edit!
-#[path = "y2024/mod.rs"]
-pub mod y2024;
 #[path = "y2023/mod.rs"]
 pub mod y2023;
-/// Selects the function for the given year, day, and part
-pub fn select_function(
+#[path = "y2024/mod.rs"]
+pub mod y2024;
+/// Selects the solution for the given year and day
+pub fn select_day(
+    year: u32,
+    day: u32,
+) -> Result<Box<dyn crate::solution::DynSolution>, String> {
+    match year {
+        2023 => Ok(y2023::select_day(day)?),
+        2024 => Ok(y2024::select_day(day)?),
+        _ => Err("Invalid year!".into()),
+    }
+}
+/// Returns the names of the variants of the given year and day
+pub fn variants(year: u32, day: u32) -> &'static [&'static str] {
+    match year {
+        2023 => y2023::variants(day),
+        2024 => y2024::variants(day),
+        _ => &[],
+    }
+}
+/// Selects the given year and day with one part replaced by the named variant
+pub fn select_variant(
     year: u32,
     day: u32,
-    part: u32,
-) -> Result<fn(String) -> String, String> {
+    name: &str,
+) -> Result<Box<dyn crate::solution::DynSolution>, String> {
     match year {
-        2024 => Ok(y2024::select_function(day, part)?),
-        2023 => Ok(y2023::select_function(day, part)?),
+        2023 => Ok(y2023::select_variant(day, name)?),
+        2024 => Ok(y2024::select_variant(day, name)?),
         _ => Err("Invalid year!".into()),
     }
 }
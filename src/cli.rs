@@ -0,0 +1,471 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_client::{calendar, SolutionPart};
+use clap::{Args, Parser, Subcommand};
+
+use crate::report::Format;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// Runs Advent of Code solutions and talks to adventofcode.com
+#[derive(Debug, Parser)]
+#[command(name = "advent", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Use the session and cache of this profile of advent.toml
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run a solution against the puzzle input
+    Run(RunArgs),
+    /// Download and cache the puzzle input
+    Fetch(FetchArgs),
+    /// Run a solution and submit its answer
+    Submit(SelectArgs),
+    /// List the registered solutions
+    List(ListArgs),
+    /// Run the unit tests of a day and check it against its saved examples
+    Test(TestArgs),
+    /// Rebuild and re-run a day whenever its source or inputs change
+    Watch(WatchArgs),
+    /// Run every variant of a part, check they agree and rank them by speed
+    Compare(CompareArgs),
+    /// Count down to the next puzzle and fetch its input as soon as it unlocks
+    Await(AwaitArgs),
+    /// Print the description of a puzzle
+    Show(ShowArgs),
+    /// Record the answers accepted for every solved day of a year
+    Answers(AnswersArgs),
+    /// Inspect and manage the cached inputs
+    #[command(subcommand)]
+    Cache(CacheCommand),
+    /// Check the session cookie, or import it from a browser, and show whose it is
+    Login(LoginArgs),
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Command::Run(RunArgs::default())
+    }
+}
+
+impl Command {
+    /// The puzzle selection of the command, if it has one
+    pub fn select_mut(&mut self) -> Option<&mut SelectArgs> {
+        match self {
+            Command::Run(args) => Some(&mut args.select),
+            Command::Submit(select) => Some(select),
+            Command::Fetch(args) => Some(&mut args.select),
+            Command::Test(args) => Some(&mut args.select),
+            Command::List(_)
+            | Command::Watch(_)
+            | Command::Compare(_)
+            | Command::Await(_)
+            | Command::Show(_)
+            | Command::Answers(_)
+            | Command::Cache(_)
+            | Command::Login(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Args)]
+pub struct SelectArgs {
+    /// Puzzle year, defaults to the current year
+    #[arg(short, long)]
+    pub year: Option<u32>,
+    /// Puzzle day, defaults to the latest puzzle during the event and 1 otherwise
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..))]
+    pub day: Option<u8>,
+    /// Puzzle part, defaults to 1
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+    /// Never read from stdin, use the defaults for anything not given
+    #[arg(long)]
+    pub no_prompt: bool,
+    /// The year from the configuration, used instead of the current year
+    #[arg(skip)]
+    pub default_year: Option<u32>,
+}
+
+#[derive(Debug, Default, Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub select: SelectArgs,
+    /// Submit the answer without asking
+    #[arg(long)]
+    pub submit: bool,
+    /// When submitting while rate limited, wait it out and submit again
+    #[arg(long)]
+    pub wait: bool,
+    /// Run every registered day and part of the year
+    #[arg(long, conflicts_with_all = ["day", "part", "submit", "wait"])]
+    pub all: bool,
+    /// Run on the input of every profile and check the answers against each one's
+    #[arg(
+        long,
+        conflicts_with_all = ["profile", "input", "stdin", "submit", "wait", "format", "output"]
+    )]
+    pub all_profiles: bool,
+    /// Read the puzzle input from a file instead of the cache, skipping the client
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["stdin", "all", "submit"])]
+    pub input: Option<PathBuf>,
+    /// Read the puzzle input from stdin, skipping the client
+    #[arg(long, conflicts_with_all = ["all", "submit"])]
+    pub stdin: bool,
+    /// Only print the answer
+    #[arg(short, long, conflicts_with = "all")]
+    pub quiet: bool,
+    /// Give up on a solution after this many seconds [default: 60]
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+    /// How to report the results [default: text]
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+    /// Write the report to this file instead of stdout
+    #[arg(long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+impl RunArgs {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS))
+    }
+
+    pub fn format(&self) -> Format {
+        self.format.unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Only list the solutions of this year
+    #[arg(short, long)]
+    pub year: Option<u32>,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    #[command(flatten)]
+    pub select: SelectArgs,
+    /// Download every unlocked day of the year that isn't cached yet
+    #[arg(long, conflicts_with_all = ["day", "part"])]
+    pub all: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TestArgs {
+    #[command(flatten)]
+    pub select: SelectArgs,
+    /// Also run the tests against the cached puzzle input
+    #[arg(long)]
+    pub input: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    pub year: u32,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+    pub day: u8,
+    /// How often to check for changes, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    pub interval: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    pub year: u32,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+    pub day: u8,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: u8,
+    /// Read the puzzle input from a file instead of the cache
+    #[arg(long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+    /// Give up on a variant after this many seconds
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_TIMEOUT_SECS)]
+    pub timeout: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct AwaitArgs {
+    /// Puzzle year, defaults to the year of the next puzzle
+    #[arg(requires = "day")]
+    pub year: Option<u32>,
+    /// Puzzle day, defaults to the next puzzle
+    #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+    pub day: Option<u8>,
+}
+
+#[derive(Debug, Args)]
+pub struct ShowArgs {
+    pub year: u32,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+    pub day: u8,
+    /// Print Markdown instead of text for the terminal
+    #[arg(long)]
+    pub markdown: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct AnswersArgs {
+    pub year: u32,
+}
+
+#[derive(Debug, Args)]
+pub struct LoginArgs {
+    /// Import the session from this Firefox cookies.sqlite and save it in the session file
+    #[arg(long, value_name = "PATH", conflicts_with = "chromium")]
+    pub firefox: Option<PathBuf>,
+    /// Import the session from this Chromium Cookies file and save it in the session file
+    #[arg(long, value_name = "PATH")]
+    pub chromium: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// List the cached inputs with when and for whom they were downloaded
+    List(CacheYearArgs),
+    /// Check the cached inputs against their metadata and look for error pages
+    Verify(CacheYearArgs),
+    /// Remove the cached inputs and puzzle pages of a year or a day
+    Clear(CacheClearArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CacheYearArgs {
+    /// Only this year, every year if not given
+    pub year: Option<u32>,
+}
+
+#[derive(Debug, Args)]
+pub struct CacheClearArgs {
+    /// Only this year, every year if not given
+    pub year: Option<u32>,
+    /// Only this day of the year
+    #[arg(requires = "year", value_parser = clap::value_parser!(u8).range(1..))]
+    pub day: Option<u8>,
+}
+
+impl SelectArgs {
+    /// Resolves the selected puzzle, prompting for anything missing unless `--no-prompt` is set
+    pub fn solution_part(&self) -> SolutionPart {
+        let SolutionPart { year, day, .. } = self.solution_day();
+        let part = self
+            .part
+            .unwrap_or_else(|| self.prompt("Enter part(default 1):", 1));
+        SolutionPart::create(year, day, part)
+    }
+
+    /// Same as [`SelectArgs::solution_part`] for commands that work on a whole day
+    pub fn solution_day(&self) -> SolutionPart {
+        let year = self.solution_year();
+        let default_day = match calendar::today(time::OffsetDateTime::now_utc()) {
+            (current_year, Some(day)) if current_year == year => day,
+            _ => 1,
+        };
+        let day = self.day.unwrap_or_else(|| {
+            self.prompt(&format!("Enter day(default {}):", default_day), default_day)
+        });
+        SolutionPart::create(year, day, self.part.unwrap_or(1))
+    }
+
+    /// Resolves the selected year only
+    pub fn solution_year(&self) -> u32 {
+        let default_year = self
+            .default_year
+            .unwrap_or_else(|| calendar::today(time::OffsetDateTime::now_utc()).0);
+        self.year.unwrap_or_else(|| {
+            self.prompt(
+                &format!("Enter year(default {}):", default_year),
+                default_year,
+            )
+        })
+    }
+
+    fn prompt<T: std::str::FromStr>(&self, prompt: &str, default: T) -> T {
+        if self.no_prompt {
+            default
+        } else {
+            prompt_for_input(prompt, default)
+        }
+    }
+}
+
+pub fn prompt_for_input<T: std::str::FromStr>(prompt: &str, default: T) -> T {
+    let mut stdin = String::new();
+    println!("{}", prompt);
+    if std::io::stdin().read_line(&mut stdin).is_err() {
+        return default;
+    }
+    stdin.trim().parse::<T>().unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_run() {
+        let cli =
+            Cli::try_parse_from(["advent", "run", "-y", "2024", "-d", "6", "-p", "2"]).unwrap();
+        let Some(Command::Run(args)) = cli.command else {
+            panic!("expected run command");
+        };
+        let SolutionPart { year, day, part } = args.select.solution_part();
+        assert_eq!((year, day, part), (2024, 6, 2));
+        assert!(!args.submit);
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        let cli = Cli::try_parse_from(["advent", "fetch", "--year", "2023", "--all"]).unwrap();
+        let Some(Command::Fetch(args)) = cli.command else {
+            panic!("expected fetch command");
+        };
+        assert_eq!(args.select.year, Some(2023));
+        assert!(args.all);
+        assert!(Cli::try_parse_from(["advent", "fetch", "--all", "--day", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_profiles() {
+        let cli = Cli::try_parse_from(["advent", "run", "--profile", "alice", "-d", "3"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("alice"));
+        let cli = Cli::try_parse_from(["advent", "run", "--all-profiles", "--all"]).unwrap();
+        let Some(Command::Run(args)) = cli.command else {
+            panic!("expected run command");
+        };
+        assert!(args.all_profiles && args.all);
+        assert!(
+            Cli::try_parse_from(["advent", "run", "--all-profiles", "--profile", "alice"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_login() {
+        let cli = Cli::try_parse_from(["advent", "login", "--firefox", "cookies.sqlite"]).unwrap();
+        let Some(Command::Login(args)) = cli.command else {
+            panic!("expected login command");
+        };
+        assert_eq!(args.firefox, Some(PathBuf::from("cookies.sqlite")));
+        assert!(
+            Cli::try_parse_from(["advent", "login", "--firefox", "a", "--chromium", "b"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_day() {
+        assert!(Cli::try_parse_from(["advent", "run", "--day", "0"]).is_err());
+        // puzzle sets other than adventofcode.com may have more days
+        assert!(Cli::try_parse_from(["advent", "run", "--day", "26"]).is_ok());
+        assert!(Cli::try_parse_from(["advent", "run", "--part", "3"]).is_err());
+        assert!(Cli::try_parse_from(["advent", "run", "--all", "--day", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_local_input_cannot_submit() {
+        let cli = Cli::try_parse_from(["advent", "run", "--input", "example.txt", "-q"]).unwrap();
+        let Some(Command::Run(args)) = cli.command else {
+            panic!("expected run command");
+        };
+        assert_eq!(args.input, Some(PathBuf::from("example.txt")));
+        assert!(args.quiet);
+        assert!(Cli::try_parse_from(["advent", "run", "--stdin", "--submit"]).is_err());
+        assert!(Cli::try_parse_from(["advent", "run", "--stdin", "--input", "a.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        let cli = Cli::try_parse_from(["advent", "run", "--all", "--format", "junit"]).unwrap();
+        let Some(Command::Run(args)) = cli.command else {
+            panic!("expected run command");
+        };
+        assert_eq!(args.format(), Format::Junit);
+        assert!(Cli::try_parse_from(["advent", "run", "--format", "yaml"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_compare() {
+        let cli = Cli::try_parse_from(["advent", "compare", "2024", "7", "2"]).unwrap();
+        let Some(Command::Compare(args)) = cli.command else {
+            panic!("expected compare command");
+        };
+        assert_eq!((args.year, args.day, args.part), (2024, 7, 2));
+        assert_eq!(args.timeout, DEFAULT_TIMEOUT_SECS);
+        assert!(Cli::try_parse_from(["advent", "compare", "2024", "7", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_await() {
+        let cli = Cli::try_parse_from(["advent", "await"]).unwrap();
+        let Some(Command::Await(args)) = cli.command else {
+            panic!("expected await command");
+        };
+        assert_eq!((args.year, args.day), (None, None));
+        let cli = Cli::try_parse_from(["advent", "await", "2025", "3"]).unwrap();
+        let Some(Command::Await(args)) = cli.command else {
+            panic!("expected await command");
+        };
+        assert_eq!((args.year, args.day), (Some(2025), Some(3)));
+        assert!(Cli::try_parse_from(["advent", "await", "2025"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_show() {
+        let cli = Cli::try_parse_from(["advent", "show", "2024", "6", "--markdown"]).unwrap();
+        let Some(Command::Show(args)) = cli.command else {
+            panic!("expected show command");
+        };
+        assert_eq!((args.year, args.day, args.markdown), (2024, 6, true));
+        assert!(Cli::try_parse_from(["advent", "show", "2024"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_cache() {
+        let cli = Cli::try_parse_from(["advent", "cache", "clear", "2024", "6"]).unwrap();
+        let Some(Command::Cache(CacheCommand::Clear(args))) = cli.command else {
+            panic!("expected cache clear command");
+        };
+        assert_eq!((args.year, args.day), (Some(2024), Some(6)));
+        let cli = Cli::try_parse_from(["advent", "cache", "list"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Cache(CacheCommand::List(CacheYearArgs {
+                year: None
+            })))
+        ));
+        assert!(Cli::try_parse_from(["advent", "cache"]).is_err());
+    }
+
+    #[test]
+    fn test_no_prompt_defaults() {
+        let args = SelectArgs {
+            year: Some(2023),
+            no_prompt: true,
+            ..Default::default()
+        };
+        let SolutionPart { year, part, .. } = args.solution_part();
+        assert_eq!((year, part), (2023, 1));
+    }
+
+    #[test]
+    fn test_configured_year() {
+        let args = SelectArgs {
+            no_prompt: true,
+            default_year: Some(2019),
+            ..Default::default()
+        };
+        assert_eq!(args.solution_year(), 2019);
+        let args = SelectArgs {
+            year: Some(2023),
+            ..args
+        };
+        assert_eq!(args.solution_year(), 2023);
+    }
+}
@@ -1,78 +1,617 @@
 mod auto_import;
-use std::time::Instant;
-use time::Month;
+mod cli;
+mod report;
+mod runner;
+mod solution;
+mod watch;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
 
-use aoc_client::{Client, SolutionPart};
+use aoc_client::{
+    calendar, AdventOfCode, Answers, Client, Config, Fetched, InputCache, Session, SolutionPart,
+    SubmitMode, SubmitOutcome, Throttle, REQUEST_INTERVAL, SESSION_VAR,
+};
+use clap::{Parser, ValueEnum};
+use cli::{
+    AnswersArgs, AwaitArgs, CacheClearArgs, CacheCommand, CacheYearArgs, Cli, Command, CompareArgs,
+    FetchArgs, ListArgs, LoginArgs, RunArgs, ShowArgs, TestArgs, WatchArgs,
+};
+use report::Format;
+use runner::{Outcome, PartRun};
 
-fn main() {
-    let solution_day = get_solution_day();
-    let SolutionPart { year, day, part } = solution_day;
-    let client = Client::new().unwrap();
-    println!("Getting input for year {} day {}...", year, day);
-    let input = client.get_input(&solution_day).unwrap();
-    // get puzzle input
-    println!(
-        "Getting function for year {} day {} part {}...",
+type AppResult = Result<(), Box<dyn Error>>;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let command = cli.command.unwrap_or_default();
+    let config = Config::load()
+        .and_then(|config| match &cli.profile {
+            Some(name) => config.profile(name),
+            None => Ok(config),
+        })
+        .map_err(|err| err.into());
+    match config.and_then(|config| dispatch(command, &config)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn dispatch(mut command: Command, config: &Config) -> AppResult {
+    if let Some(select) = command.select_mut() {
+        select.default_year = config.year;
+    }
+    match command {
+        Command::Run(args) => run(args, config),
+        Command::Fetch(args) => fetch(args, config),
+        Command::Submit(select) => run(
+            RunArgs {
+                select,
+                submit: true,
+                ..Default::default()
+            },
+            config,
+        ),
+        Command::List(args) => list(args),
+        Command::Test(args) => test(args, config),
+        Command::Watch(args) => watch(args, config),
+        Command::Compare(args) => compare(args, config),
+        Command::Await(args) => await_puzzle(args, config),
+        Command::Show(args) => show(args, config),
+        Command::Answers(args) => answers(args, config),
+        Command::Cache(command) => cache(command, config),
+        Command::Login(args) => login(args, config),
+    }
+}
+
+fn run(mut args: RunArgs, config: &Config) -> AppResult {
+    if args.format.is_none() {
+        args.format =
+            match &config.format {
+                Some(format) => Some(Format::from_str(format, true).map_err(|err| {
+                    format!("invalid format {} in configuration: {}", format, err)
+                })?),
+                None => None,
+            };
+    }
+    if args.all_profiles {
+        return run_profiles(args, config);
+    }
+    if args.all {
+        return run_all(args, config);
+    }
+    let reporting = args.format() != Format::Text || args.output.is_some();
+    // stdin carries the input and quiet output must stay clean, so neither can prompt
+    args.select.no_prompt |= args.stdin || args.quiet || reporting;
+    let say = |message: String| {
+        if !args.quiet && !reporting {
+            println!("{}", message);
+        }
+    };
+    let solution_part = args.select.solution_part();
+    let SolutionPart { year, day, part } = solution_part;
+    // get solution first so a missing one fails before any network request
+    say(format!(
+        "Getting solution for year {} day {} part {}...",
         year, day, part
-    );
+    ));
+    auto_import::select_day(year, day as u32)?;
+    // get puzzle input
+    let (input, client) = match (&args.input, args.stdin) {
+        (Some(path), _) => {
+            say(format!("Reading input from {}...", path.display()));
+            let input = fs::read_to_string(path)
+                .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+            (input, None)
+        }
+        (None, true) => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            (input, None)
+        }
+        (None, false) => {
+            let client = Client::with_config(config)?;
+            say(format!("Getting input for year {} day {}...", year, day));
+            (client.get_input(&solution_part)?, Some(client))
+        }
+    };
     // run puzzle solution
-    let func = auto_import::select_function(year, day as u32, part as u32).unwrap();
-    println!("Running function...");
-    let now = Instant::now();
-    let result = func(input);
-    println!("Completed in: {}ms", now.elapsed().as_secs_f64() * 1000.0);
-    // print result
-    println!("Result: {}", result);
-    // submit result?
-    if !prompt_for_input(
-        "Press enter to exit or type anything to submit answer",
-        "".to_string(),
-    )
-    .is_empty()
-    {
-        let res = client.submit_solution(&solution_day, &result).unwrap();
-        println!("{}", res);
-    }
-}
-
-fn get_solution_day() -> SolutionPart {
-    let args = std::env::args().collect::<Vec<String>>();
-    let current_time = time::OffsetDateTime::now_utc();
-    // get year
-    let current_year = current_time.year() as u32;
-    let prompt = format!("Enter year(default {}):", current_year);
-    let year = match args.get(1) {
-        Some(arg) => arg
-            .parse::<u32>()
-            .unwrap_or_else(|_| prompt_for_input(&prompt, current_year)),
-        None => prompt_for_input(&prompt, current_year),
+    say("Running solution...".to_string());
+    let run = runner::run_part(solution_part, input, args.timeout());
+    if let Some(parsed_in) = run.parsed_in {
+        say(format!("Parsed in: {}", runner::format_duration(parsed_in)));
+    }
+    say(format!(
+        "Completed in: {}",
+        runner::format_duration(run.elapsed)
+    ));
+    if reporting {
+        write_report(
+            std::slice::from_ref(&run),
+            args.format(),
+            args.output.as_deref(),
+        )?;
+    }
+    let Outcome::Answer(result) = &run.outcome else {
+        return Err(run.outcome.summary().into());
     };
-    // get day
-    let default_day = if current_time.month() == Month::December {
-        current_time.day()
-    } else {
-        1
+    // print result, unless the report already has it
+    if args.quiet {
+        println!("{}", result);
+    } else if !reporting {
+        println!("Result: {}", result);
+    }
+    // submit result? answers of local inputs are never submitted
+    let Some(client) = client else {
+        return Ok(());
     };
-    let prompt = format!("Enter day(default {}):", default_day);
-    let day = if let Some(arg) = args.get(2) {
-        arg.parse::<u8>()
-            .unwrap_or_else(|_| prompt_for_input(&prompt, default_day))
+    let accepted = client.accepted_answers(year)?;
+    for difference in runner::differing_answers(std::slice::from_ref(&run), &accepted) {
+        eprintln!("Warning: {}", difference);
+    }
+    let submit = args.submit
+        || match config.submit {
+            SubmitMode::Always => true,
+            SubmitMode::Never => false,
+            SubmitMode::Ask => {
+                !args.select.no_prompt
+                    && !cli::prompt_for_input(
+                        "Press enter to exit or type anything to submit answer",
+                        "".to_string(),
+                    )
+                    .is_empty()
+            }
+        };
+    if !submit {
+        return Ok(());
+    }
+    let outcome = if args.wait {
+        let mut counting = false;
+        let outcome = client.submit_waiting(&solution_part, result, |wait| {
+            if !args.quiet && !reporting {
+                counting = true;
+                print!(
+                    "\rRate limited, submitting again in {}s ",
+                    wait.as_secs() + 1
+                );
+                let _ = io::stdout().flush();
+            }
+        })?;
+        if counting {
+            println!();
+        }
+        outcome
     } else {
-        prompt_for_input(&prompt, default_day)
+        client.submit_solution(&solution_part, result)?
+    };
+    if let SubmitOutcome::RateLimited { .. } = outcome {
+        return Err(format!("{} Use --wait to submit once it is over.", outcome).into());
+    }
+    match outcome {
+        SubmitOutcome::Correct | SubmitOutcome::AlreadySolved | SubmitOutcome::Unknown(_) => {
+            say(outcome.to_string());
+            Ok(())
+        }
+        _ => Err(outcome.to_string().into()),
+    }
+}
+
+fn run_all(args: RunArgs, config: &Config) -> AppResult {
+    let year = args.select.solution_year();
+    if args.format() == Format::Text {
+        println!("Running every solution for year {}...", year);
+    }
+    // a client that can't be made only leaves the days it would have got the input of missing
+    let client = Client::with_config(config);
+    let runs = runner::run_year(year, args.timeout(), |solution_part| {
+        let client = client.as_ref().map_err(|err| err.to_string())?;
+        client
+            .get_input(&solution_part)
+            .map_err(|err| err.to_string())
+    });
+    if runs.is_empty() {
+        return Err(format!("no solutions registered for year {}", year).into());
+    }
+    write_report(&runs, args.format(), args.output.as_deref())?;
+    let differences = runner::differing_answers(&runs, &Answers::load(&config.cache_dir, year)?);
+    for difference in &differences {
+        eprintln!("{}", difference);
+    }
+    if !differences.is_empty() {
+        return Err(format!(
+            "{} answers differ from the accepted ones",
+            differences.len()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Runs the part, or every part of the year with `--all`, on the input of every profile
+///
+/// A solution can be right for one input and wrong for another, so any answer differing from
+/// the one accepted for the account of the profile fails the run.
+fn run_profiles(args: RunArgs, config: &Config) -> AppResult {
+    let solution_part = (!args.all).then(|| args.select.solution_part());
+    let year = match solution_part {
+        Some(solution_part) => {
+            auto_import::select_day(solution_part.year, solution_part.day as u32)?;
+            solution_part.year
+        }
+        None => args.select.solution_year(),
+    };
+    let mut problems = vec![];
+    for profile in config.all_profiles()? {
+        let name = profile.profile_name();
+        println!("Profile {}:", name);
+        let client = match Client::with_config(&profile) {
+            Ok(client) => client,
+            Err(err) => {
+                problems.push(format!("{}: {}", name, err));
+                continue;
+            }
+        };
+        let runs = match solution_part {
+            Some(solution_part) => match client.get_input(&solution_part) {
+                Ok(input) => vec![runner::run_part(solution_part, input, args.timeout())],
+                Err(err) => {
+                    problems.push(format!("{}: {}", name, err));
+                    continue;
+                }
+            },
+            None => runner::run_year(year, args.timeout(), |solution_part| {
+                client
+                    .get_input(&solution_part)
+                    .map_err(|err| err.to_string())
+            }),
+        };
+        print!("{}", runner::format_table(&runs));
+        for run in &runs {
+            if !matches!(run.outcome, Outcome::Answer(_)) {
+                let SolutionPart { day, part, .. } = run.solution_part;
+                let reason = match &run.outcome {
+                    Outcome::Missing(reason) => reason.clone(),
+                    outcome => outcome.summary(),
+                };
+                problems.push(format!("{}: day {} part {}: {}", name, day, part, reason));
+            }
+        }
+        for difference in runner::differing_answers(&runs, &client.accepted_answers(year)?) {
+            problems.push(format!("{}: {}", name, difference));
+        }
+    }
+    for problem in &problems {
+        eprintln!("{}", problem);
+    }
+    if !problems.is_empty() {
+        return Err(format!(
+            "{} problems with the inputs of the profiles",
+            problems.len()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Writes the report to the file if one is given, or to stdout
+fn write_report(runs: &[PartRun], format: Format, output: Option<&Path>) -> AppResult {
+    let report = report::render(runs, format);
+    match output {
+        Some(path) => fs::write(path, report)
+            .map_err(|err| format!("could not write {}: {}", path.display(), err))?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+fn fetch(args: FetchArgs, config: &Config) -> AppResult {
+    if args.all {
+        return fetch_year(args.select.solution_year(), config);
+    }
+    let solution_part = args.select.solution_day();
+    let SolutionPart { year, day, .. } = solution_part;
+    let client = Client::with_config(config)?;
+    println!("Getting input for year {} day {}...", year, day);
+    let input = client.get_input(&solution_part)?;
+    println!("Got {} lines", input.lines().count());
+    // the input is what matters, examples are a bonus
+    match client.get_examples(year, day) {
+        Ok(examples) => println!("Saved {} examples", examples.len()),
+        Err(err) => eprintln!("Could not save the examples: {}", err),
+    }
+    Ok(())
+}
+
+/// Downloads every missing input of the year, running it again resumes after the last day
+fn fetch_year(year: u32, config: &Config) -> AppResult {
+    let client = Client::with_config(config)?;
+    println!("Getting every input of {}...", year);
+    let (mut downloaded, mut failed) = (0, 0);
+    client.fetch_year(year, |day, fetched| match fetched {
+        Fetched::Cached => println!("day {}: cached", day),
+        Fetched::Downloaded(lines) => {
+            downloaded += 1;
+            println!("day {}: got {} lines", day, lines);
+        }
+        Fetched::Failed(err) => {
+            failed += 1;
+            eprintln!("day {}: {}", day, err);
+        }
+    })?;
+    println!("Downloaded {} inputs", downloaded);
+    if failed > 0 {
+        return Err(format!(
+            "{} inputs could not be downloaded, run it again to retry",
+            failed
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn list(args: ListArgs) -> AppResult {
+    let current_year = time::OffsetDateTime::now_utc().year() as u32;
+    let years = match args.year {
+        Some(year) => year..=year,
+        None => calendar::FIRST_YEAR..=current_year,
+    };
+    for year in years {
+        let mut days: Vec<(u8, Vec<String>)> = vec![];
+        for (day, part) in runner::registered_parts(year) {
+            match days.last_mut() {
+                Some((last, parts)) if *last == day => parts.push(part.to_string()),
+                _ => days.push((day, vec![part.to_string()])),
+            }
+        }
+        if !days.is_empty() {
+            let days: Vec<String> = days
+                .into_iter()
+                .map(|(day, mut parts)| {
+                    parts.extend(
+                        auto_import::variants(year, day as u32)
+                            .iter()
+                            .map(|name| name.to_string()),
+                    );
+                    format!("d{}({})", day, parts.join(","))
+                })
+                .collect();
+            println!("{}: {}", year, days.join(" "));
+        }
+    }
+    Ok(())
+}
+
+fn test(args: TestArgs, config: &Config) -> AppResult {
+    let SolutionPart { year, day, .. } = args.select.solution_day();
+    let filter = format!("auto_import::y{}::d{}::", year, day);
+    let mut command = std::process::Command::new(env!("CARGO"));
+    command.args(["test", "--bin", env!("CARGO_PKG_NAME")]);
+    if args.input {
+        command.args(["--features", "test_input"]);
+    }
+    let status = command
+        .arg("--")
+        .arg(&filter)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .status()?;
+    if !status.success() {
+        return Err(format!("tests for year {} day {} failed", year, day).into());
+    }
+    test_examples(year, day, config)
+}
+
+/// Runs the day on the examples saved by `advent fetch`
+fn test_examples(year: u32, day: u8, config: &Config) -> AppResult {
+    let examples = aoc_client::Example::load(&config.cache_dir, year, day)?;
+    if examples.is_empty() {
+        println!("No saved examples, `advent fetch` saves them from the puzzle page");
+        return Ok(());
+    }
+    let mut failed = 0;
+    for example in &examples {
+        let (run, passed) = runner::run_example(year, day, example, Duration::from_secs(60));
+        if passed {
+            println!("example {} ... ok", example.name(day));
+        } else {
+            failed += 1;
+            println!(
+                "example {} ... expected {}, got {}",
+                example.name(day),
+                example.answer,
+                run.outcome.summary()
+            );
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{} of {} examples failed", failed, examples.len()).into());
+    }
+    Ok(())
+}
+
+fn watch(args: WatchArgs, config: &Config) -> AppResult {
+    let interval = Duration::from_millis(args.interval);
+    watch::Watcher::new(args.year, args.day, config, interval).watch()
+}
+
+fn compare(args: CompareArgs, config: &Config) -> AppResult {
+    let solution_part = SolutionPart::create(args.year, args.day, args.part);
+    let input = match &args.input {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| format!("could not read {}: {}", path.display(), err))?,
+        None => Client::with_config(config)?.get_input(&solution_part)?,
     };
-    // get part
-    let part = if let Some(arg) = args.get(3) {
-        arg.parse::<u8>()
-            .unwrap_or_else(|_| prompt_for_input("Enter part(default 1):", default_day))
+    let timeout = Duration::from_secs(args.timeout);
+    let variants = runner::run_variants(solution_part, input, timeout);
+    print!("{}", runner::format_comparison(&variants));
+    if variants.len() < 2 {
+        println!(
+            "No variants registered, add a function like part{}_fast",
+            args.part
+        );
+    }
+    if !runner::variants_agree(&variants) {
+        return Err("the variants don't agree on the answer".into());
+    }
+    Ok(())
+}
+
+fn await_puzzle(args: AwaitArgs, config: &Config) -> AppResult {
+    let (year, day) = args
+        .year
+        .zip(args.day)
+        .unwrap_or_else(|| calendar::next_unlock(time::OffsetDateTime::now_utc()));
+    let solution_part = SolutionPart::create(year, day, 1);
+    let client = Client::with_config(config)?;
+    let mut counting = false;
+    let input = client.wait_for_input(&solution_part, |wait| {
+        counting = true;
+        print!(
+            "\rDay {} of {} unlocks in {} ",
+            day,
+            year,
+            calendar::format_wait(wait + Duration::from_secs(1))
+        );
+        let _ = io::stdout().flush();
+    })?;
+    if counting {
+        println!();
+    }
+    println!(
+        "Got {} lines of input for year {} day {}",
+        input.lines().count(),
+        year,
+        day
+    );
+    Ok(())
+}
+
+fn show(args: ShowArgs, config: &Config) -> AppResult {
+    let puzzle = Client::with_config(config)?.get_puzzle(args.year, args.day)?;
+    if args.markdown {
+        print!("{}", puzzle.to_markdown());
     } else {
-        prompt_for_input("Enter part(default 1):", 1)
+        print!("{}", puzzle.to_terminal());
+    }
+    Ok(())
+}
+
+fn answers(args: AnswersArgs, config: &Config) -> AppResult {
+    let client = Client::with_config(config)?;
+    for day in client.unlocked_days(args.year)? {
+        let answers = client.get_answers(args.year, day)?;
+        if !answers.is_empty() {
+            println!("Day {}: {}", day, answers.join(", "));
+        }
+    }
+    Ok(())
+}
+
+fn cache(command: CacheCommand, config: &Config) -> AppResult {
+    let cache = InputCache::from_config(config);
+    match command {
+        CacheCommand::List(CacheYearArgs { year }) => {
+            for entry in cache.entries(year)? {
+                let details = match &entry.metadata {
+                    Some(metadata) => format!(
+                        "{} bytes, downloaded {} for {}",
+                        metadata.length,
+                        format_timestamp(metadata.downloaded_at),
+                        metadata.account
+                    ),
+                    None => "no metadata".to_string(),
+                };
+                let details = match entry.encrypted {
+                    true => format!("{}, encrypted", details),
+                    false => details,
+                };
+                println!("{} day {:>2}: {}", entry.year, entry.day, details);
+            }
+            Ok(())
+        }
+        CacheCommand::Verify(CacheYearArgs { year }) => {
+            let entries = cache.entries(year)?;
+            let broken: Vec<_> = entries
+                .iter()
+                .filter_map(|entry| Some((entry, entry.problem.as_ref()?)))
+                .collect();
+            for (entry, problem) in &broken {
+                println!("{} {}", entry.path.display(), problem);
+            }
+            if !broken.is_empty() {
+                return Err(format!(
+                    "{} of {} cached inputs are broken, they are downloaded again when needed",
+                    broken.len(),
+                    entries.len()
+                )
+                .into());
+            }
+            println!("{} cached inputs are fine", entries.len());
+            Ok(())
+        }
+        CacheCommand::Clear(CacheClearArgs { year, day }) => {
+            let removed = cache.clear(year, day)?;
+            println!("Removed {} cached days", removed);
+            Ok(())
+        }
+    }
+}
+
+/// Checks the session with adventofcode.com, saving it first if it comes from a browser
+fn login(args: LoginArgs, config: &Config) -> AppResult {
+    let (session, source, imported) = match (&args.firefox, &args.chromium) {
+        (Some(path), _) => (
+            Session::from_firefox(path)?,
+            path.display().to_string(),
+            true,
+        ),
+        (_, Some(path)) => (
+            Session::from_chromium(path)?,
+            path.display().to_string(),
+            true,
+        ),
+        (None, None) => {
+            let source = match config.session {
+                Some(_) => SESSION_VAR.to_string(),
+                None => config.session_file.display().to_string(),
+            };
+            (config.session()?, source, false)
+        }
     };
-    SolutionPart::create(year, day, part)
+    println!("Checking the session from {}...", source);
+    let user = AdventOfCode::new(&session.token)
+        .with_throttle(Throttle::new(config.throttle_file(), REQUEST_INTERVAL))
+        .user()?;
+    println!("Logged in as {}", user);
+    match session.expires {
+        // only a browser knows when the cookie expires, a saved one lasts about a month
+        Some(expires) if imported => println!("Expires on {}", format_timestamp(expires)),
+        Some(expires) => println!("Expires around {}", format_timestamp(expires)),
+        None => println!("Sessions last about a month"),
+    }
+    if imported {
+        session.save(&config.session_file)?;
+        println!("Saved the session to {}", config.session_file.display());
+    }
+    Ok(())
 }
 
-fn prompt_for_input<T: std::str::FromStr>(prompt: &str, default: T) -> T {
-    let mut stdin = String::new();
-    println!("{}", prompt);
-    std::io::stdin().read_line(&mut stdin).unwrap();
-    stdin.trim().parse::<T>().unwrap_or(default)
+/// Formats seconds since the Unix epoch as a UTC date and time
+fn format_timestamp(seconds: u64) -> String {
+    match time::OffsetDateTime::from_unix_timestamp(seconds as i64) {
+        Ok(at) => format!(
+            "{}-{:02}-{:02} {:02}:{:02} UTC",
+            at.year(),
+            at.month() as u8,
+            at.day(),
+            at.hour(),
+            at.minute()
+        ),
+        Err(_) => seconds.to_string(),
+    }
 }
@@ -0,0 +1,270 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use aoc_client::SolutionPart;
+
+use crate::runner::{format_table, total_duration, Outcome, PartRun};
+
+/// How run results are written out
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable text
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Markdown,
+    /// JUnit XML, for CI systems
+    Junit,
+}
+
+/// One run, flattened for serialization
+#[derive(Debug, Serialize)]
+struct Row<'a> {
+    year: u32,
+    day: u8,
+    part: u8,
+    outcome: &'static str,
+    answer: Option<String>,
+    /// Time spent parsing the input of the day, shared by its parts
+    parse_ms: Option<f64>,
+    duration_ms: f64,
+    input_hash: Option<&'a str>,
+    /// Why the run has no answer
+    detail: Option<String>,
+}
+
+impl<'a> From<&'a PartRun> for Row<'a> {
+    fn from(run: &'a PartRun) -> Self {
+        let SolutionPart { year, day, part } = run.solution_part;
+        let (answer, detail) = match &run.outcome {
+            Outcome::Answer(answer) => (Some(answer.to_string().trim().to_string()), None),
+            Outcome::Missing(reason) => (None, Some(reason.clone())),
+            outcome => (None, Some(outcome.summary())),
+        };
+        Row {
+            year,
+            day,
+            part,
+            outcome: run.outcome.kind(),
+            answer,
+            parse_ms: run
+                .parsed_in
+                .map(|parsed_in| parsed_in.as_secs_f64() * 1000.0),
+            duration_ms: run.elapsed.as_secs_f64() * 1000.0,
+            input_hash: run.input_hash.as_deref(),
+            detail,
+        }
+    }
+}
+
+/// Renders a batch of runs in the given format
+pub fn render(runs: &[PartRun], format: Format) -> String {
+    let rows: Vec<Row> = runs.iter().map(Row::from).collect();
+    let total_ms = total_duration(runs).as_secs_f64() * 1000.0;
+    match format {
+        Format::Text => format_table(runs),
+        Format::Json => {
+            let mut json = serde_json::to_string_pretty(&rows).unwrap_or_default();
+            json.push('\n');
+            json
+        }
+        Format::Csv => render_csv(&rows),
+        Format::Markdown => render_markdown(&rows, total_ms),
+        Format::Junit => render_junit(&rows, total_ms),
+    }
+}
+
+fn render_csv(rows: &[Row]) -> String {
+    let mut csv =
+        "year,day,part,outcome,answer,parse_ms,duration_ms,input_hash,detail\n".to_string();
+    for row in rows {
+        let fields = [
+            row.year.to_string(),
+            row.day.to_string(),
+            row.part.to_string(),
+            row.outcome.to_string(),
+            row.answer.clone().unwrap_or_default(),
+            row.parse_ms
+                .map(|parse_ms| format!("{:.3}", parse_ms))
+                .unwrap_or_default(),
+            format!("{:.3}", row.duration_ms),
+            row.input_hash.unwrap_or_default().to_string(),
+            row.detail.clone().unwrap_or_default(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_escape(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_markdown(rows: &[Row], total_ms: f64) -> String {
+    let mut markdown = "| Year | Day | Part | Answer | Parse | Time |\n\
+                        | ---: | --: | ---: | :----- | ----: | ---: |\n"
+        .to_string();
+    for row in rows {
+        let answer = match &row.answer {
+            Some(answer) => format!("`{}`", answer),
+            None => format!("_{}_", row.outcome.replace('_', " ")),
+        };
+        let parse = match row.parse_ms {
+            Some(parse_ms) => format!("{:.3}ms", parse_ms),
+            None => "-".to_string(),
+        };
+        let time = match row.answer {
+            Some(_) => format!("{:.3}ms", row.duration_ms),
+            None => "-".to_string(),
+        };
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            row.year,
+            row.day,
+            row.part,
+            answer.replace('|', "\\|").replace('\n', "<br>"),
+            parse,
+            time
+        ));
+    }
+    markdown.push_str(&format!("\nTotal: {:.3}ms\n", total_ms));
+    markdown
+}
+
+fn render_junit(rows: &[Row], total_ms: f64) -> String {
+    let failures = rows.iter().filter(|row| row.outcome == "timed_out").count();
+    let errors = rows.iter().filter(|row| row.outcome == "panicked").count();
+    let skipped = rows.iter().filter(|row| row.outcome == "missing").count();
+    let mut xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n".to_string();
+    xml.push_str(&format!(
+        "<testsuite name=\"advent\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+        rows.len(),
+        failures,
+        errors,
+        skipped,
+        total_ms / 1000.0
+    ));
+    for row in rows {
+        xml.push_str(&format!(
+            "  <testcase classname=\"y{}.d{}\" name=\"part {}\" time=\"{:.6}\">\n",
+            row.year,
+            row.day,
+            row.part,
+            row.duration_ms / 1000.0
+        ));
+        let detail = xml_escape(row.detail.as_deref().unwrap_or_default());
+        match row.outcome {
+            "missing" => xml.push_str(&format!("    <skipped message=\"{}\"/>\n", detail)),
+            "panicked" => xml.push_str(&format!(
+                "    <error type=\"panic\" message=\"{}\"/>\n",
+                detail
+            )),
+            "timed_out" => xml.push_str(&format!(
+                "    <failure type=\"timeout\" message=\"{}\"/>\n",
+                detail
+            )),
+            _ => xml.push_str(&format!(
+                "    <system-out>{}</system-out>\n",
+                xml_escape(row.answer.as_deref().unwrap_or_default())
+            )),
+        }
+        xml.push_str("  </testcase>\n");
+    }
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_client::Answer;
+    use std::time::Duration;
+
+    fn runs() -> Vec<PartRun> {
+        vec![
+            PartRun {
+                solution_part: SolutionPart::create(2024, 6, 1),
+                outcome: Outcome::Answer(Answer::Integer(41)),
+                elapsed: Duration::from_millis(3),
+                parsed_in: Some(Duration::from_millis(2)),
+                input_hash: Some("abc".to_string()),
+            },
+            PartRun {
+                solution_part: SolutionPart::create(2024, 6, 2),
+                outcome: Outcome::Panicked(
+                    "a, \"b\"".to_string(),
+                    "src/y2024/d6.rs:1:1".to_string(),
+                ),
+                elapsed: Duration::from_millis(1),
+                parsed_in: Some(Duration::from_millis(2)),
+                input_hash: Some("abc".to_string()),
+            },
+            PartRun {
+                solution_part: SolutionPart::create(2024, 7, 1),
+                outcome: Outcome::Missing("no input".to_string()),
+                elapsed: Duration::ZERO,
+                parsed_in: None,
+                input_hash: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json: serde_json::Value = serde_json::from_str(&render(&runs(), Format::Json)).unwrap();
+        assert_eq!(json[0]["answer"], "41");
+        assert_eq!(json[0]["duration_ms"], 3.0);
+        assert_eq!(json[0]["parse_ms"], 2.0);
+        assert_eq!(json[0]["input_hash"], "abc");
+        assert_eq!(json[1]["outcome"], "panicked");
+        assert_eq!(json[1]["answer"], serde_json::Value::Null);
+        assert_eq!(json[2]["parse_ms"], serde_json::Value::Null);
+        assert_eq!(json[2]["detail"], "no input");
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let csv = render(&runs(), Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[1], "2024,6,1,answer,41,2.000,3.000,abc,");
+        assert_eq!(
+            lines[2],
+            "2024,6,2,panicked,,2.000,1.000,abc,\"panicked at src/y2024/d6.rs:1:1: a, \"\"b\"\"\""
+        );
+        assert_eq!(lines[3], "2024,7,1,missing,,,0.000,,no input");
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render(&runs(), Format::Markdown);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[2], "| 2024 | 6 | 1 | `41` | 2.000ms | 3.000ms |");
+        assert_eq!(lines[3], "| 2024 | 6 | 2 | _panicked_ | 2.000ms | - |");
+        assert_eq!(lines[4], "| 2024 | 7 | 1 | _missing_ | - | - |");
+        assert_eq!(lines[6], "Total: 6.000ms");
+    }
+
+    #[test]
+    fn test_render_junit() {
+        let xml = render(&runs(), Format::Junit);
+        assert!(xml.contains("tests=\"3\" failures=\"0\" errors=\"1\" skipped=\"1\""));
+        assert!(xml.contains("<system-out>41</system-out>"));
+        assert!(xml.contains("message=\"panicked at src/y2024/d6.rs:1:1: a, &quot;b&quot;\""));
+        assert!(xml.contains("<skipped message=\"no input\"/>"));
+    }
+}
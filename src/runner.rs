@@ -0,0 +1,697 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic;
+use std::sync::{mpsc, Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_client::{Answer, Answers, Example, SolutionPart};
+use sha2::{Digest, Sha256};
+
+use crate::auto_import;
+use crate::solution::DynSolution;
+
+/// Name of the threads solutions run on, so the panic hook can tell them apart
+const SOLUTION_THREAD: &str = "solution";
+/// Solutions tend to recurse deeply, so they get more stack than the default 2MiB
+const SOLUTION_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// What came out of running one part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(Answer),
+    Missing(String),
+    /// The panic message and the location it was raised at
+    Panicked(String, String),
+    TimedOut(Duration),
+}
+
+impl Outcome {
+    /// A short description for tables, the answer itself when there is one
+    pub fn summary(&self) -> String {
+        match self {
+            Outcome::Answer(Answer::Glyphs(_)) => "glyphs".to_string(),
+            Outcome::Answer(answer) => answer.to_string(),
+            Outcome::Missing(_) => "missing".to_string(),
+            Outcome::Panicked(message, location) => {
+                format!("panicked at {}: {}", location, message)
+            }
+            Outcome::TimedOut(elapsed) => format!("timed out after {}", format_duration(*elapsed)),
+        }
+    }
+
+    /// A stable name of the variant for reports
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Outcome::Answer(_) => "answer",
+            Outcome::Missing(_) => "missing",
+            Outcome::Panicked(..) => "panicked",
+            Outcome::TimedOut(_) => "timed_out",
+        }
+    }
+}
+
+/// A single part that was run, or skipped because something was missing
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub solution_part: SolutionPart,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    /// Time spent parsing the input of the day, shared by its parts
+    pub parsed_in: Option<Duration>,
+    /// SHA-256 of the input, if there was one
+    pub input_hash: Option<String>,
+}
+
+/// Hex encoded SHA-256 of an input, to tell which input a report was made with
+pub fn hash_input(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+/// Returns every (day, part) the generated `select_day` knows about for the year
+pub fn registered_parts(year: u32) -> Vec<(u8, u8)> {
+    (1..=u8::MAX)
+        .filter(|day| auto_import::select_day(year, *day as u32).is_ok())
+        .flat_map(|day| (1..=2).map(move |part| (day, part)))
+        .collect()
+}
+
+/// Runs a task on its own thread and times it
+///
+/// A panic is caught and reported, and a task that doesn't finish within the timeout is
+/// abandoned. An abandoned thread keeps running in the background until the process exits.
+pub fn isolate<T: Send + 'static>(
+    task: impl FnOnce() -> T + Send + 'static,
+    timeout: Duration,
+) -> (Result<T, Outcome>, Duration) {
+    install_panic_hook();
+    let (sender, receiver) = mpsc::channel();
+    let now = Instant::now();
+    let spawned = thread::Builder::new()
+        .name(SOLUTION_THREAD.to_string())
+        .stack_size(SOLUTION_STACK_SIZE)
+        .spawn(move || {
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(task)).map_err(|payload| {
+                let location = PANIC_LOCATION.with(|location| location.borrow_mut().take());
+                (panic_message(payload), location.unwrap_or_default())
+            });
+            // the receiver is gone if the task timed out
+            let _ = sender.send((result, now.elapsed()));
+        });
+    if let Err(err) = spawned {
+        let message = format!("could not spawn solution thread: {}", err);
+        return (
+            Err(Outcome::Panicked(message, String::new())),
+            Duration::ZERO,
+        );
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok((Ok(value), elapsed)) => (Ok(value), elapsed),
+        Ok((Err((message, location)), elapsed)) => {
+            (Err(Outcome::Panicked(message, location)), elapsed)
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            let elapsed = now.elapsed();
+            (Err(Outcome::TimedOut(elapsed)), elapsed)
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            let message = "solution thread exited without an answer".to_string();
+            (
+                Err(Outcome::Panicked(message, String::new())),
+                now.elapsed(),
+            )
+        }
+    }
+}
+
+/// Records panic locations of solution threads instead of printing them
+fn install_panic_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() == Some(SOLUTION_THREAD) {
+                let location = info.location().map(|l| l.to_string());
+                PANIC_LOCATION.with(|cell| *cell.borrow_mut() = location);
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs the given part, reporting a missing solution instead of failing
+pub fn run_part(solution_part: SolutionPart, input: String, timeout: Duration) -> PartRun {
+    let SolutionPart { year, day, part } = solution_part;
+    run_day(year, day, input, &[part], timeout).remove(0)
+}
+
+/// Parses the input of a day once and runs the given parts on it
+///
+/// Parsing and every part run isolated and are timed separately. If parsing fails, every part
+/// reports the failure.
+pub fn run_day(year: u32, day: u8, input: String, parts: &[u8], timeout: Duration) -> Vec<PartRun> {
+    match auto_import::select_day(year, day as u32) {
+        Ok(solution) => run_solution(solution.into(), year, day, input, parts, timeout),
+        Err(err) => missing_parts(year, day, parts, err, Some(hash_input(&input))),
+    }
+}
+
+/// Describes every run of the year whose answer differs from the one Advent of Code accepted
+pub fn differing_answers(runs: &[PartRun], accepted: &Answers) -> Vec<String> {
+    runs.iter()
+        .filter_map(|run| {
+            let SolutionPart { year, day, part } = run.solution_part;
+            let Outcome::Answer(answer) = &run.outcome else {
+                return None;
+            };
+            let expected = accepted.get(day, part).filter(|_| year == accepted.year)?;
+            let answer = answer.submission().ok()?;
+            (answer != expected).then(|| {
+                format!(
+                    "{} day {} part {}: {} differs from the accepted answer {}",
+                    year, day, part, answer, expected
+                )
+            })
+        })
+        .collect()
+}
+
+/// Runs a part on one of its saved examples, and tells whether it gave the expected answer
+pub fn run_example(year: u32, day: u8, example: &Example, timeout: Duration) -> (PartRun, bool) {
+    let run = run_day(year, day, example.input.clone(), &[example.part], timeout)
+        .pop()
+        .unwrap();
+    let passed =
+        matches!(&run.outcome, Outcome::Answer(answer) if answer.to_string() == example.answer);
+    (run, passed)
+}
+
+/// Runs every variant of a part on the same input, the default implementation first
+///
+/// Each variant parses the input itself, so its parse time can be compared too.
+pub fn run_variants(solution_part: SolutionPart, input: String, timeout: Duration) -> Vec<Variant> {
+    let SolutionPart { year, day, part } = solution_part;
+    let default_name = format!("part{}", part);
+    let mut variants = vec![(
+        default_name.clone(),
+        auto_import::select_day(year, day as u32),
+    )];
+    for name in auto_import::variants(year, day as u32) {
+        if name
+            .strip_prefix(&default_name)
+            .is_some_and(|suffix| suffix.starts_with('_'))
+        {
+            variants.push((
+                name.to_string(),
+                auto_import::select_variant(year, day as u32, name),
+            ));
+        }
+    }
+    variants
+        .into_iter()
+        .map(|(name, solution)| {
+            let run = match solution {
+                Ok(solution) => {
+                    run_solution(solution.into(), year, day, input.clone(), &[part], timeout)
+                        .remove(0)
+                }
+                Err(err) => {
+                    missing_parts(year, day, &[part], err, Some(hash_input(&input))).remove(0)
+                }
+            };
+            Variant { name, run }
+        })
+        .collect()
+}
+
+/// One implementation of a part and how it did
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub name: String,
+    pub run: PartRun,
+}
+
+/// Whether every variant found the same answer
+pub fn variants_agree(variants: &[Variant]) -> bool {
+    let mut outcomes = variants.iter().map(|variant| &variant.run.outcome);
+    let first = outcomes.next();
+    matches!(first, Some(Outcome::Answer(_))) && outcomes.all(|outcome| Some(outcome) == first)
+}
+
+/// Parts that couldn't run, all with the same reason
+fn missing_parts(
+    year: u32,
+    day: u8,
+    parts: &[u8],
+    reason: String,
+    input_hash: Option<String>,
+) -> Vec<PartRun> {
+    parts
+        .iter()
+        .map(|part| PartRun {
+            solution_part: SolutionPart::create(year, day, *part),
+            outcome: Outcome::Missing(reason.clone()),
+            elapsed: Duration::ZERO,
+            parsed_in: None,
+            input_hash: input_hash.clone(),
+        })
+        .collect()
+}
+
+fn run_solution(
+    solution: Arc<dyn DynSolution>,
+    year: u32,
+    day: u8,
+    input: String,
+    parts: &[u8],
+    timeout: Duration,
+) -> Vec<PartRun> {
+    let input_hash = Some(hash_input(&input));
+    let (parsed, parsed_in) = {
+        let solution = solution.clone();
+        isolate(move || solution.parse(input), timeout)
+    };
+    parts
+        .iter()
+        .map(|&part| {
+            let (outcome, elapsed) = match &parsed {
+                Ok(parsed) => {
+                    let (solution, parsed) = (solution.clone(), parsed.clone());
+                    match isolate(move || solution.part(part, &parsed), timeout) {
+                        (Ok(Some(answer)), elapsed) => (Outcome::Answer(answer), elapsed),
+                        (Ok(None), _) => (Outcome::Missing("Invalid part!".into()), Duration::ZERO),
+                        (Err(outcome), elapsed) => (outcome, elapsed),
+                    }
+                }
+                Err(outcome) => (outcome.clone(), Duration::ZERO),
+            };
+            PartRun {
+                solution_part: SolutionPart::create(year, day, part),
+                outcome,
+                elapsed,
+                parsed_in: Some(parsed_in),
+                input_hash: input_hash.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Runs every registered part of a year, getting each input once per day
+///
+/// A day whose input can't be had is reported missing with the reason, the others still run.
+pub fn run_year(
+    year: u32,
+    timeout: Duration,
+    mut get_input: impl FnMut(SolutionPart) -> Result<String, String>,
+) -> Vec<PartRun> {
+    let mut days: Vec<(u8, Vec<u8>)> = vec![];
+    for (day, part) in registered_parts(year) {
+        match days.last_mut() {
+            Some((last, parts)) if *last == day => parts.push(part),
+            _ => days.push((day, vec![part])),
+        }
+    }
+    let mut runs = vec![];
+    for (day, parts) in days {
+        match get_input(SolutionPart::create(year, day, parts[0])) {
+            Ok(input) => runs.extend(run_day(year, day, input, &parts, timeout)),
+            Err(err) => runs.extend(missing_parts(
+                year,
+                day,
+                &parts,
+                format!("no input: {}", err),
+                None,
+            )),
+        }
+    }
+    runs
+}
+
+/// Total time of the runs, counting the parse time of each day once
+pub fn total_duration(runs: &[PartRun]) -> Duration {
+    let mut parsed: Vec<(u32, u8)> = vec![];
+    let mut total = Duration::ZERO;
+    for run in runs {
+        let SolutionPart { year, day, .. } = run.solution_part;
+        total += run.elapsed;
+        if let Some(parsed_in) = run.parsed_in {
+            if !parsed.contains(&(year, day)) {
+                parsed.push((year, day));
+                total += parsed_in;
+            }
+        }
+    }
+    total
+}
+
+/// Formats runs as a table with the answer, the elapsed time and the total time
+pub fn format_table(runs: &[PartRun]) -> String {
+    let answer_width = runs
+        .iter()
+        .map(|run| run.outcome.summary().len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+    let mut table = format!(
+        "{:>4} | {:>3} | {:>4} | {:<answer_width$} | {:>12} | {:>12}\n",
+        "Year", "Day", "Part", "Answer", "Parse", "Time"
+    );
+    table.push_str(&format!("{}\n", "-".repeat(answer_width + 52)));
+    for run in runs {
+        let SolutionPart { year, day, part } = run.solution_part;
+        let parse = match run.parsed_in {
+            Some(parsed_in) => format_duration(parsed_in),
+            None => "-".to_string(),
+        };
+        let time = match &run.outcome {
+            Outcome::Missing(_) => "-".to_string(),
+            _ => format_duration(run.elapsed),
+        };
+        let answer = run.outcome.summary();
+        table.push_str(&format!(
+            "{:>4} | {:>3} | {:>4} | {:<answer_width$} | {:>12} | {:>12}\n",
+            year, day, part, answer, parse, time
+        ));
+    }
+    let total = total_duration(runs);
+    table.push_str(&format!("Total: {}\n", format_duration(total)));
+    table
+}
+
+/// Formats variants as a table ranked by speed, relative to the fastest one
+///
+/// Variants without an answer are ranked last.
+pub fn format_comparison(variants: &[Variant]) -> String {
+    let mut ranked: Vec<&Variant> = variants.iter().collect();
+    ranked.sort_by_key(|variant| match variant.run.outcome {
+        Outcome::Answer(_) => (false, variant.run.elapsed),
+        _ => (true, Duration::ZERO),
+    });
+    let name_width = ranked
+        .iter()
+        .map(|variant| variant.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Variant".len());
+    let answer_width = ranked
+        .iter()
+        .map(|variant| variant.run.outcome.summary().len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+    let mut table = format!(
+        "{:<name_width$} | {:<answer_width$} | {:>12} | {:>12} | {:>8}\n",
+        "Variant", "Answer", "Parse", "Time", "Relative"
+    );
+    table.push_str(&format!("{}\n", "-".repeat(name_width + answer_width + 45)));
+    let fastest = ranked.first().map(|variant| variant.run.elapsed);
+    for variant in ranked {
+        let run = &variant.run;
+        let parse = match run.parsed_in {
+            Some(parsed_in) => format_duration(parsed_in),
+            None => "-".to_string(),
+        };
+        let (time, relative) = match (&run.outcome, fastest) {
+            (Outcome::Answer(_), Some(fastest)) if !fastest.is_zero() => (
+                format_duration(run.elapsed),
+                format!("{:.2}x", run.elapsed.as_secs_f64() / fastest.as_secs_f64()),
+            ),
+            (Outcome::Answer(_), _) => (format_duration(run.elapsed), "-".to_string()),
+            _ => ("-".to_string(), "-".to_string()),
+        };
+        table.push_str(&format!(
+            "{:<name_width$} | {:<answer_width$} | {:>12} | {:>12} | {:>8}\n",
+            variant.name,
+            run.outcome.summary(),
+            parse,
+            time,
+            relative
+        ));
+    }
+    table
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_parts() {
+        let parts = registered_parts(2024);
+        assert!(parts.contains(&(6, 1)));
+        assert!(parts.contains(&(6, 2)));
+        assert!(!parts.contains(&(25, 1)));
+        assert!(registered_parts(1999).is_empty());
+    }
+
+    #[test]
+    fn test_run_year_without_inputs() {
+        let runs = run_year(2024, Duration::from_secs(1), |_| {
+            Err("no session".to_string())
+        });
+        assert_eq!(runs.len(), registered_parts(2024).len());
+        assert!(runs
+            .iter()
+            .all(|run| run.outcome == Outcome::Missing("no input: no session".to_string())));
+    }
+
+    #[test]
+    fn test_differing_answers() {
+        let cache_dir =
+            std::env::temp_dir().join(format!("advent-accepted-{}", std::process::id()));
+        let mut accepted = Answers::load(&cache_dir, 2024).unwrap();
+        accepted
+            .record(7, vec!["3749".to_string(), "11387".to_string()])
+            .unwrap();
+        let run = |part, answer: i64| PartRun {
+            solution_part: SolutionPart::create(2024, 7, part),
+            outcome: Outcome::Answer(Answer::from(answer)),
+            elapsed: Duration::ZERO,
+            parsed_in: None,
+            input_hash: None,
+        };
+        let runs = [run(1, 3749), run(2, 11386)];
+        assert_eq!(
+            differing_answers(&runs, &accepted),
+            vec!["2024 day 7 part 2: 11386 differs from the accepted answer 11387"]
+        );
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_example() {
+        let example = Example {
+            part: 1,
+            index: 1,
+            input: "190: 10 19\n3267: 81 40 27\n83: 17 5\n".to_string(),
+            answer: "3457".to_string(),
+        };
+        let (run, passed) = run_example(2024, 7, &example, Duration::from_secs(10));
+        assert_eq!(run.outcome, Outcome::Answer(Answer::from(3457)));
+        assert!(passed);
+    }
+
+    /// Every example saved in the cache, so fetched examples are tested without copying them
+    #[test]
+    #[cfg(feature = "test_input")]
+    fn test_saved_examples() {
+        let config = aoc_client::Config::load().expect("could not load the configuration");
+        let current_year = time::OffsetDateTime::now_utc().year() as u32;
+        let mut failures = vec![];
+        for year in aoc_client::calendar::FIRST_YEAR..=current_year {
+            let days =
+                (1..=u8::MAX).filter(|day| auto_import::select_day(year, *day as u32).is_ok());
+            for day in days {
+                let examples = Example::load(&config.cache_dir, year, day)
+                    .unwrap_or_else(|err| panic!("examples of {} day {}: {}", year, day, err));
+                for example in examples {
+                    let (run, passed) = run_example(year, day, &example, Duration::from_secs(60));
+                    if !passed {
+                        failures.push(format!(
+                            "{} {}: expected {}, got {}",
+                            year,
+                            example.name(day),
+                            example.answer,
+                            run.outcome.summary()
+                        ));
+                    }
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_run_part_missing() {
+        let run = run_part(
+            SolutionPart::create(2024, 25, 1),
+            "".to_string(),
+            Duration::from_secs(1),
+        );
+        assert_eq!(run.outcome, Outcome::Missing("Invalid day!".to_string()));
+        assert_eq!(
+            run.input_hash.as_deref(),
+            Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+    }
+
+    #[test]
+    fn test_run_day() {
+        let runs = run_day(
+            2024,
+            7,
+            "190: 10 19\n7290: 6 8 6 15\n".to_string(),
+            &[1, 2],
+            Duration::from_secs(1),
+        );
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].outcome, Outcome::Answer(Answer::Integer(190)));
+        assert_eq!(runs[1].outcome, Outcome::Answer(Answer::Integer(7480)));
+        assert!(runs[0].parsed_in.is_some());
+        assert_eq!(runs[0].parsed_in, runs[1].parsed_in);
+    }
+
+    #[test]
+    fn test_run_day_parse_panicked() {
+        let runs = run_day(
+            2024,
+            7,
+            "no numbers".to_string(),
+            &[1, 2],
+            Duration::from_secs(1),
+        );
+        assert_eq!(runs[0].outcome.kind(), "panicked");
+        assert_eq!(runs[0].outcome, runs[1].outcome);
+        assert_eq!(runs[1].elapsed, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_run_variants() {
+        let input = "190: 10 19\n7290: 6 8 6 15\n".to_string();
+        let variants = run_variants(
+            SolutionPart::create(2024, 7, 2),
+            input.clone(),
+            Duration::from_secs(1),
+        );
+        let names: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["part2", "part2_fast"]);
+        assert!(variants_agree(&variants));
+        let variants = run_variants(
+            SolutionPart::create(2024, 7, 1),
+            input,
+            Duration::from_secs(1),
+        );
+        assert_eq!(variants.len(), 1);
+    }
+
+    #[test]
+    fn test_format_comparison() {
+        let variant = |name: &str, outcome: Outcome, millis| Variant {
+            name: name.to_string(),
+            run: PartRun {
+                solution_part: SolutionPart::create(2024, 7, 2),
+                outcome,
+                elapsed: Duration::from_millis(millis),
+                parsed_in: Some(Duration::from_millis(1)),
+                input_hash: None,
+            },
+        };
+        let variants = vec![
+            variant("part2", Outcome::Answer(Answer::Integer(7)), 8),
+            variant("part2_slow", Outcome::TimedOut(Duration::from_millis(9)), 9),
+            variant("part2_fast", Outcome::Answer(Answer::Integer(7)), 2),
+        ];
+        assert!(!variants_agree(&variants));
+        assert!(variants_agree(&variants[..1]));
+        let table = format_comparison(&variants);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[2].starts_with("part2_fast | 7 "));
+        assert!(lines[2].ends_with("|    1.00x"));
+        assert!(lines[3].ends_with("|      8.000ms |    4.00x"));
+        assert!(lines[4].ends_with("|            - |        -"));
+    }
+
+    #[test]
+    fn test_isolate_answer() {
+        let (result, _) = isolate(|| "abc".len(), Duration::from_secs(1));
+        assert_eq!(result, Ok(3));
+    }
+
+    #[test]
+    fn test_isolate_panicked() {
+        let (result, _) = isolate(|| "abc".parse::<u32>().unwrap(), Duration::from_secs(1));
+        let Err(Outcome::Panicked(message, location)) = result else {
+            panic!("expected a panic, got {:?}", result);
+        };
+        assert!(message.contains("InvalidDigit"));
+        assert!(location.starts_with("src/runner.rs:"));
+    }
+
+    #[test]
+    fn test_isolate_timed_out() {
+        let (result, elapsed) = isolate(
+            || thread::sleep(Duration::from_secs(1)),
+            Duration::from_millis(10),
+        );
+        assert_eq!(result, Err(Outcome::TimedOut(elapsed)));
+        assert!(elapsed < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let runs = vec![
+            PartRun {
+                solution_part: SolutionPart::create(2024, 1, 1),
+                outcome: Outcome::Answer(Answer::Integer(11)),
+                elapsed: Duration::from_millis(2),
+                parsed_in: Some(Duration::from_millis(1)),
+                input_hash: None,
+            },
+            PartRun {
+                solution_part: SolutionPart::create(2024, 1, 2),
+                outcome: Outcome::Answer(Answer::Integer(31)),
+                elapsed: Duration::from_millis(3),
+                parsed_in: Some(Duration::from_millis(1)),
+                input_hash: None,
+            },
+            PartRun {
+                solution_part: SolutionPart::create(2024, 2, 1),
+                outcome: Outcome::Missing("no input".to_string()),
+                elapsed: Duration::ZERO,
+                parsed_in: None,
+                input_hash: None,
+            },
+        ];
+        let table = format_table(&runs);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[2],
+            "2024 |   1 |    1 | 11      |      1.000ms |      2.000ms"
+        );
+        assert_eq!(
+            lines[4],
+            "2024 |   2 |    1 | missing |            - |            -"
+        );
+        assert_eq!(lines[5], "Total: 6.000ms");
+    }
+}
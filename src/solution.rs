@@ -0,0 +1,193 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use aoc_client::Answer;
+
+/// A day that parses its input once and shares the result between both parts
+///
+/// The build script registers every type that implements this trait in a day module, so
+/// the runner can time parsing and each part separately.
+///
+/// ```ignore
+/// pub struct BridgeRepair;
+///
+/// impl Solution for BridgeRepair {
+///     type Parsed = Vec<Vec<i64>>;
+///
+///     fn parse(input: String) -> Self::Parsed { ... }
+///     fn part1(equations: &Self::Parsed) -> Answer { ... }
+///     fn part2(equations: &Self::Parsed) -> Answer { ... }
+/// }
+/// ```
+pub trait Solution {
+    type Parsed: Send + Sync + 'static;
+
+    fn parse(input: String) -> Self::Parsed;
+    fn part1(parsed: &Self::Parsed) -> Answer;
+    fn part2(parsed: &Self::Parsed) -> Answer;
+}
+
+/// Parsed input of any day, shared between the threads the parts run on
+pub type AnyParsed = Arc<dyn Any + Send + Sync>;
+
+/// A [`Solution`] with its `Parsed` type erased, as returned by the generated `select_day`
+pub trait DynSolution: Send + Sync {
+    fn parse(&self, input: String) -> AnyParsed;
+    /// Runs a part on input returned by `parse`, `None` if the part doesn't exist
+    fn part(&self, part: u8, parsed: &AnyParsed) -> Option<Answer>;
+}
+
+/// Erases the `Parsed` type of a [`Solution`]
+pub struct Typed<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> Typed<S> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: Solution> Default for Typed<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Solution> DynSolution for Typed<S> {
+    fn parse(&self, input: String) -> AnyParsed {
+        Arc::new(S::parse(input))
+    }
+
+    fn part(&self, part: u8, parsed: &AnyParsed) -> Option<Answer> {
+        let parsed = parsed.downcast_ref::<S::Parsed>()?;
+        match part {
+            1 => Some(S::part1(parsed)),
+            2 => Some(S::part2(parsed)),
+            _ => None,
+        }
+    }
+}
+
+/// A [`Solution`] with one part replaced by a named variant, like `part2_fast`
+pub struct Variant<S: Solution> {
+    part: u8,
+    func: fn(&S::Parsed) -> Answer,
+}
+
+impl<S: Solution> Variant<S> {
+    pub fn new(part: u8, func: fn(&S::Parsed) -> Answer) -> Self {
+        Self { part, func }
+    }
+}
+
+impl<S: Solution> DynSolution for Variant<S> {
+    fn parse(&self, input: String) -> AnyParsed {
+        Arc::new(S::parse(input))
+    }
+
+    fn part(&self, part: u8, parsed: &AnyParsed) -> Option<Answer> {
+        let parsed = parsed.downcast_ref::<S::Parsed>()?;
+        match part {
+            part if part == self.part => Some((self.func)(parsed)),
+            1 => Some(S::part1(parsed)),
+            2 => Some(S::part2(parsed)),
+            _ => None,
+        }
+    }
+}
+
+/// Adapts days written as `fn(String) -> Answer` functions, their parse step keeps the input
+pub struct Functions {
+    pub part1: fn(String) -> Answer,
+    pub part2: fn(String) -> Answer,
+}
+
+impl DynSolution for Functions {
+    fn parse(&self, input: String) -> AnyParsed {
+        Arc::new(input)
+    }
+
+    fn part(&self, part: u8, parsed: &AnyParsed) -> Option<Answer> {
+        let input = parsed.downcast_ref::<String>()?.clone();
+        match part {
+            1 => Some((self.part1)(input)),
+            2 => Some((self.part2)(input)),
+            _ => None,
+        }
+    }
+}
+
+/// Parses and runs part 1 in one go, for tests of a [`Solution`]
+#[cfg(test)]
+pub fn part1<S: Solution>(input: String) -> Answer {
+    S::part1(&S::parse(input))
+}
+
+/// Parses and runs part 2 in one go, for tests of a [`Solution`]
+#[cfg(test)]
+pub fn part2<S: Solution>(input: String) -> Answer {
+    S::part2(&S::parse(input))
+}
+
+/// The cached input of a day, decrypted if the cache is encrypted, for tests on real inputs
+#[cfg(all(test, feature = "test_input"))]
+pub fn cached_input(year: u32, day: u8) -> String {
+    let config = aoc_client::Config::load().expect("could not load the configuration");
+    aoc_client::InputCache::from_config(&config)
+        .load(year, day)
+        .unwrap_or_else(|err| panic!("{}, `advent fetch` caches it", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        type Parsed = Vec<usize>;
+
+        fn parse(input: String) -> Self::Parsed {
+            input.lines().map(|line| line.len()).collect()
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Answer {
+            parsed.iter().sum::<usize>().into()
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Answer {
+            parsed.iter().max().copied().unwrap_or(0).into()
+        }
+    }
+
+    #[test]
+    fn test_typed() {
+        let day = Typed::<Lengths>::new();
+        let parsed = day.parse("ab\ncde".to_string());
+        assert_eq!(day.part(1, &parsed), Some(Answer::Integer(5)));
+        assert_eq!(day.part(2, &parsed), Some(Answer::Integer(3)));
+        assert_eq!(day.part(3, &parsed), None);
+        assert_eq!(part1::<Lengths>("ab\ncde".to_string()), "5");
+    }
+
+    #[test]
+    fn test_variant() {
+        let day = Variant::<Lengths>::new(2, |parsed| parsed.len().into());
+        let parsed = day.parse("ab\ncde".to_string());
+        assert_eq!(day.part(1, &parsed), Some(Answer::Integer(5)));
+        assert_eq!(day.part(2, &parsed), Some(Answer::Integer(2)));
+    }
+
+    #[test]
+    fn test_functions() {
+        let day = Functions {
+            part1: |input| input.len().into(),
+            part2: |input| input.into(),
+        };
+        let parsed = day.parse("abc".to_string());
+        assert_eq!(day.part(1, &parsed), Some(Answer::Integer(3)));
+        assert_eq!(day.part(2, &parsed), Some(Answer::Text("abc".to_string())));
+        let other: AnyParsed = Arc::new(1);
+        assert_eq!(day.part(1, &other), None);
+    }
+}
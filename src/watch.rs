@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc_client::{Config, InputCache};
+
+use crate::runner::format_duration;
+
+/// The answer and time of one part, or the reason it could not run
+type PartResult = Result<(String, Duration), String>;
+
+/// Re-runs both parts of a day whenever its source, its input or its examples change
+pub struct Watcher {
+    year: u32,
+    day: u8,
+    root: PathBuf,
+    cache_dir: PathBuf,
+    /// Decrypts the cached input when it is encrypted
+    cache: InputCache,
+    /// Profile of the parent, so the runs use the same account
+    profile: Option<String>,
+    interval: Duration,
+    previous: HashMap<(String, u8), PartResult>,
+}
+
+impl Watcher {
+    pub fn new(year: u32, day: u8, config: &Config, interval: Duration) -> Self {
+        Self {
+            year,
+            day,
+            root: PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+            cache_dir: config.cache_dir.clone(),
+            cache: InputCache::from_config(config),
+            profile: config.profile.clone(),
+            interval,
+            previous: HashMap::new(),
+        }
+    }
+
+    /// Polls forever, only returning if the watched files can't be read
+    pub fn watch(&mut self) -> Result<(), Box<dyn Error>> {
+        let source = self.source_path();
+        if !source.exists() {
+            return Err(format!("{} does not exist", source.display()).into());
+        }
+        println!("Watching {}, press ctrl-c to stop", source.display());
+        let mut last_snapshot = None;
+        loop {
+            let snapshot = self.snapshot();
+            if last_snapshot.as_ref() != Some(&snapshot) {
+                last_snapshot = Some(snapshot);
+                self.rebuild_and_run();
+            }
+            thread::sleep(self.interval);
+        }
+    }
+
+    fn source_path(&self) -> PathBuf {
+        self.root
+            .join(format!("src/y{}/d{}.rs", self.year, self.day))
+    }
+
+    /// Example inputs first, then the real input if it is cached
+    fn inputs(&self) -> Vec<PathBuf> {
+        let examples_dir = self.cache_dir.join(format!("examples/y{}", self.year));
+        let mut inputs: Vec<PathBuf> = fs_entries(&examples_dir)
+            .into_iter()
+            .filter(|path| is_day_file(path, self.day))
+            .collect();
+        inputs.sort();
+        let input = self
+            .cache_dir
+            .join(format!("y{}/d{}.txt", self.year, self.day));
+        let encrypted = input.with_extension("txt.enc");
+        if input.exists() {
+            inputs.push(input);
+        } else if encrypted.exists() {
+            inputs.push(encrypted);
+        }
+        inputs
+    }
+
+    fn snapshot(&self) -> Vec<(PathBuf, Option<SystemTime>)> {
+        let mut paths = self.inputs();
+        paths.push(self.source_path());
+        paths
+            .into_iter()
+            .map(|path| {
+                let modified = path.metadata().and_then(|m| m.modified()).ok();
+                (path, modified)
+            })
+            .collect()
+    }
+
+    fn rebuild_and_run(&mut self) {
+        println!("Rebuilding...");
+        let Some(binary) = self.build() else {
+            println!("Build failed, waiting for changes...");
+            return;
+        };
+        let inputs = self.inputs();
+        if inputs.is_empty() {
+            println!(
+                "No example or cached input for year {} day {}",
+                self.year, self.day
+            );
+        }
+        let mut rows = vec![];
+        for input in inputs {
+            let name = input
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            for part in 1..=2 {
+                let result = self.run_part(&binary, &input, part);
+                let previous = self.previous.insert((name.clone(), part), result.clone());
+                rows.push((name.clone(), part, result, previous));
+            }
+        }
+        print!("{}", format_rows(&rows));
+    }
+
+    /// Builds the binary and returns the path cargo gives it
+    ///
+    /// Cargo replaces the binary on every build, so the running one may not be on disk anymore
+    /// and its parts have to run with the new one.
+    fn build(&self) -> Option<PathBuf> {
+        let mut build = Command::new(env!("CARGO"));
+        build
+            .args(["build", "--quiet", "--bin", env!("CARGO_PKG_NAME")])
+            .arg("--message-format=json-render-diagnostics")
+            .current_dir(&self.root)
+            .stderr(Stdio::inherit());
+        if !cfg!(debug_assertions) {
+            build.arg("--release");
+        }
+        let output = build
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|message| message["target"]["name"] == env!("CARGO_PKG_NAME"))
+            .find_map(|message| message["executable"].as_str().map(PathBuf::from))
+    }
+
+    /// Runs a part with the binary in a child process, given the input on stdin so it never
+    /// builds a client and can't reach adventofcode.com or submit anything
+    fn run_part(&self, binary: &Path, input: &Path, part: u8) -> PartResult {
+        let text = match input
+            .extension()
+            .is_some_and(|extension| extension == "enc")
+        {
+            true => self
+                .cache
+                .load(self.year, self.day)
+                .map_err(|err| err.to_string())?,
+            false => fs::read_to_string(input).map_err(|err| err.to_string())?,
+        };
+        let mut command = Command::new(binary);
+        command
+            .args(["run", "--stdin"])
+            .args(["--year", &self.year.to_string()])
+            .args(["--day", &self.day.to_string()])
+            .args(["--part", &part.to_string()])
+            // the output is parsed, whatever format is configured
+            .args(["--format", "text"]);
+        if let Some(profile) = &self.profile {
+            command.args(["--profile", profile]);
+        }
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| err.to_string())?;
+        // the child only prints a few lines before reading stdin, so its output can't fill up
+        // the pipe meanwhile, and a child that exits early is reported from its stderr
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        let output = child.wait_with_output().map_err(|err| err.to_string())?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(stderr.lines().next().unwrap_or("failed").to_string());
+        }
+        parse_run_output(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+fn fs_entries(dir: &Path) -> Vec<PathBuf> {
+    match dir.read_dir() {
+        Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Matches `d6.txt` and `d6_*.txt` but not `d61.txt`
+fn is_day_file(path: &Path, day: u8) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let Some(stem) = name.strip_suffix(".txt") else {
+        return false;
+    };
+    let prefix = format!("d{}", day);
+    stem == prefix || stem.starts_with(&format!("{}_", prefix))
+}
+
+/// Reads the answer and time from the output of `advent run`
+fn parse_run_output(stdout: &str) -> PartResult {
+    let mut elapsed = None;
+    let mut answer = None;
+    for line in stdout.lines() {
+        if let Some(ms) = line
+            .strip_prefix("Completed in: ")
+            .and_then(|ms| ms.strip_suffix("ms"))
+        {
+            elapsed = ms.parse::<f64>().ok();
+        }
+        if let Some(result) = line.strip_prefix("Result: ") {
+            answer = Some(result.to_string());
+        }
+    }
+    match (answer, elapsed) {
+        (Some(answer), Some(ms)) => Ok((answer, Duration::from_secs_f64(ms / 1000.0))),
+        _ => Err("no result".to_string()),
+    }
+}
+
+fn answer_of(result: &PartResult) -> Result<&String, &String> {
+    result.as_ref().map(|(answer, _)| answer)
+}
+
+fn format_result(result: Option<&PartResult>) -> (String, String) {
+    match result {
+        Some(Ok((answer, elapsed))) => (answer.clone(), format_duration(*elapsed)),
+        Some(Err(err)) => (format!("error: {}", err), "-".to_string()),
+        None => ("-".to_string(), "-".to_string()),
+    }
+}
+
+fn format_rows(rows: &[(String, u8, PartResult, Option<PartResult>)]) -> String {
+    let mut table = format!(
+        "{:<16} | {:>4} | {:<20} | {:>12} | {:<20} | {:>12}\n",
+        "Input", "Part", "Answer", "Time", "Previous", "Previous time"
+    );
+    for (name, part, result, previous) in rows {
+        let (answer, time) = format_result(Some(result));
+        let (previous_answer, previous_time) = format_result(previous.as_ref());
+        let changed = match previous {
+            Some(previous) if answer_of(previous) != answer_of(result) => " *",
+            _ => "",
+        };
+        table.push_str(&format!(
+            "{:<16} | {:>4} | {:<20} | {:>12} | {:<20} | {:>12}{}\n",
+            name, part, answer, time, previous_answer, previous_time, changed
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_day_file() {
+        assert!(is_day_file(Path::new("examples/d6.txt"), 6));
+        assert!(is_day_file(Path::new("examples/d6_1.txt"), 6));
+        assert!(!is_day_file(Path::new("examples/d61.txt"), 6));
+        assert!(!is_day_file(Path::new("examples/d6.rs"), 6));
+    }
+
+    #[test]
+    fn test_parse_run_output() {
+        let stdout = "Running function...\nCompleted in: 1.5ms\nResult: 41\n";
+        assert_eq!(
+            parse_run_output(stdout),
+            Ok(("41".to_string(), Duration::from_micros(1500)))
+        );
+        assert!(parse_run_output("Running function...\n").is_err());
+    }
+
+    #[test]
+    fn test_format_rows_marks_changes() {
+        let rows = vec![
+            (
+                "d6.txt".to_string(),
+                1,
+                Ok(("41".to_string(), Duration::from_millis(1))),
+                Some(Ok(("40".to_string(), Duration::from_millis(2)))),
+            ),
+            (
+                "d6.txt".to_string(),
+                2,
+                Ok(("6".to_string(), Duration::from_millis(1))),
+                Some(Ok(("6".to_string(), Duration::from_millis(3)))),
+            ),
+            (
+                "d6_1.txt".to_string(),
+                1,
+                Ok(("41".to_string(), Duration::from_millis(1))),
+                None,
+            ),
+        ];
+        let table = format_rows(&rows);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[1].ends_with(" *"));
+        assert!(lines[1].contains("40"));
+        assert!(!lines[2].ends_with(" *"));
+        assert!(!lines[3].ends_with(" *"));
+    }
+
+    #[test]
+    fn test_run_after_rebuild() {
+        let root = std::env::temp_dir().join(format!("advent-watch-{}", std::process::id()));
+        let mut config = Config::with_root(root.clone());
+        config.cache_dir = root.join(".data");
+        let example = config.cache_dir.join("examples/y2024/d7_1.txt");
+        fs::create_dir_all(example.parent().unwrap()).unwrap();
+        fs::write(&example, "190: 10 19\n3267: 81 40 27\n83: 17 5\n").unwrap();
+        let watcher = Watcher::new(2024, 7, &config, Duration::ZERO);
+        let binary = watcher.build().expect("the build failed");
+        assert_eq!(
+            answer_of(&watcher.run_part(&binary, &example, 1)),
+            Ok(&"3457".to_string())
+        );
+        // like cargo relinking it, the binary is a new file at the same path after a rebuild
+        let relinked = root.join("relinked");
+        fs::copy(&binary, &relinked).unwrap();
+        fs::remove_file(&binary).unwrap();
+        fs::rename(&relinked, &binary).unwrap();
+        let binary = watcher.build().expect("the build failed");
+        assert_eq!(
+            answer_of(&watcher.run_part(&binary, &example, 1)),
+            Ok(&"3457".to_string())
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
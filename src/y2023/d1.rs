@@ -1,10 +1,13 @@
 #[allow(unused_imports)]
 use std::{error::Error, fs};
 
+use aoc_client::Answer;
+
 pub struct Input {
     a: String,
 }
 
+#[allow(clippy::upper_case_acronyms)]
 pub enum Number {
     One,
     Two,
@@ -76,7 +79,7 @@ impl Input {
         let length = self.a.len();
         for c in 0..length {
             let a = self.a.chars().nth(c).unwrap();
-            if a.is_digit(10) {
+            if a.is_ascii_digit() {
                 num = num * 10 + a.to_digit(10).unwrap();
                 break;
             }
@@ -88,7 +91,7 @@ impl Input {
         }
         for c in (0..length).rev() {
             let a = self.a.chars().nth(c).unwrap();
-            if a.is_digit(10) {
+            if a.is_ascii_digit() {
                 num = num * 10 + a.to_digit(10).unwrap();
                 break;
             }
@@ -102,22 +105,22 @@ impl Input {
     }
 }
 
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let lines: Vec<String> = input.split('\n').map(|s| s.to_string()).collect();
     let mut sum = 0;
     for line in lines.iter() {
         sum += Input::create(line.to_string()).number();
     }
-    sum.to_string()
+    sum.into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let lines: Vec<String> = input.split('\n').map(|s| s.to_string()).collect();
     let mut sum = 0;
     for line in lines.iter() {
         sum += Input::create(line.to_string()).number();
     }
-    sum.to_string()
+    sum.into()
 }
 
 #[cfg(test)]
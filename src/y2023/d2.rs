@@ -1,3 +1,5 @@
+use aoc_client::Answer;
+
 #[derive(Debug)]
 pub struct Game {
     pub sets: Vec<Vec<(Qube, u32)>>,
@@ -81,7 +83,7 @@ impl TotalQubes {
     }
 }
 
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let lines = input
         .split('\n')
         .map(|s| s.to_string())
@@ -109,10 +111,10 @@ pub fn part1(input: String) -> String {
             sum += game.game;
         }
     }
-    sum.to_string()
+    sum.into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let lines = input
         .split('\n')
         .map(|s| s.to_string())
@@ -132,5 +134,5 @@ pub fn part2(input: String) -> String {
         let total_qube = TotalQubes::create(&game);
         sum_part2 += total_qube.red * total_qube.green * total_qube.blue;
     }
-    sum_part2.to_string()
+    sum_part2.into()
 }
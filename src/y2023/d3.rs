@@ -1,5 +1,7 @@
 use std::fmt::{Display, Formatter};
 
+use aoc_client::Answer;
+
 #[derive(Debug, Clone)]
 pub struct Input {
     line: Vec<NumberOrSymbol>,
@@ -145,7 +147,7 @@ pub struct Symbol {
     adjacent: Vec<u32>,
 }
 
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let lines: Vec<String> = input.split('\n').map(|s| s.to_string()).collect();
     let mut inputs: Vec<Input> = Vec::new();
     for line_str in lines {
@@ -211,9 +213,9 @@ pub fn part1(input: String) -> String {
         inputs.push(Input::create(line));
     }
     let mut inputs = Inputs::create(inputs);
-    inputs.sum().0.to_string()
+    inputs.sum().0.into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     part1(input)
 }
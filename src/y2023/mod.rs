@@ -2,30 +2,52 @@
 pub mod d1;
 pub mod d2;
 pub mod d3;
-/// Selects the function for the given day and part
-pub fn select_function(day: u32, part: u32) -> Result<fn(String) -> String, String> {
+/// Selects the solution for the given day
+pub fn select_day(day: u32) -> Result<Box<dyn crate::solution::DynSolution>, String> {
     match day {
         1 => {
-            match part {
-                1 => Ok(d1::part1),
-                2 => Ok(d1::part2),
-                _ => Err("Invalid part!".into()),
-            }
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d1::part1,
+                    part2: d1::part2,
+                }),
+            )
         }
         2 => {
-            match part {
-                1 => Ok(d2::part1),
-                2 => Ok(d2::part2),
-                _ => Err("Invalid part!".into()),
-            }
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d2::part1,
+                    part2: d2::part2,
+                }),
+            )
         }
         3 => {
-            match part {
-                1 => Ok(d3::part1),
-                2 => Ok(d3::part2),
-                _ => Err("Invalid part!".into()),
-            }
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d3::part1,
+                    part2: d3::part2,
+                }),
+            )
         }
         _ => Err("Invalid day!".into()),
     }
 }
+/// Returns the names of the variants of the given day
+pub fn variants(day: u32) -> &'static [&'static str] {
+    match day {
+        1 => &[],
+        2 => &[],
+        3 => &[],
+        _ => &[],
+    }
+}
+/// Selects the given day with one part replaced by the named variant
+#[allow(clippy::match_single_binding)]
+pub fn select_variant(
+    day: u32,
+    name: &str,
+) -> Result<Box<dyn crate::solution::DynSolution>, String> {
+    match (day, name) {
+        _ => Err("Invalid variant!".into()),
+    }
+}
@@ -1,4 +1,6 @@
-pub fn part1(input: String) -> String {
+use aoc_client::Answer;
+
+pub fn part1(input: String) -> Answer {
     let (mut first_list, mut second_list) = get_two_lists_from_input(input);
     first_list.sort();
     second_list.sort();
@@ -7,16 +9,16 @@ pub fn part1(input: String) -> String {
         .zip(second_list.iter())
         .map(|(f, s)| (f - s).abs())
         .sum();
-    distance.to_string()
+    distance.into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let (first_list, second_list) = get_two_lists_from_input(input);
     let mut sum = 0;
     for i in first_list.iter() {
         sum += i * second_list.iter().filter(|s| *s == i).count() as i32;
     }
-    sum.to_string()
+    sum.into()
 }
 
 fn get_two_lists_from_input(input: String) -> (Vec<i32>, Vec<i32>) {
@@ -69,18 +71,12 @@ mod tests {
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
-        assert_eq!(
-            part1(include_str!("../../.data/y2024/d1.txt").to_string()),
-            "2285373"
-        );
+        assert_eq!(part1(crate::solution::cached_input(2024, 1)), "2285373");
     }
 
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
-        assert_eq!(
-            part2(include_str!("../../.data/y2024/d1.txt").to_string()),
-            "21142653"
-        );
+        assert_eq!(part2(crate::solution::cached_input(2024, 1)), "21142653");
     }
 }
@@ -1,32 +1,41 @@
+use aoc_client::Answer;
 use parser::{Direction, Map, MultiLineParser, Point};
 use std::collections::HashSet;
 
-pub fn part1(input: String) -> String {
-    let (mut parser, map) = parse_input(&input);
-    let starts = map.get(&'0').unwrap();
+use crate::solution::Solution;
 
-    let sum = starts
-        .iter()
-        .map(|p| dfs(&mut parser, p, '9'))
-        .sum::<usize>();
-    sum.to_string()
-}
+pub struct HoofIt;
 
-pub fn part2(input: String) -> String {
-    let (mut parser, map) = parse_input(&input);
-    let starts = map.get(&'0').unwrap();
+impl Solution for HoofIt {
+    type Parsed = (MultiLineParser, Map);
 
-    let sum = starts
-        .iter()
-        .map(|p| dfs_part2(&mut parser, p, '9'))
-        .sum::<usize>();
-    sum.to_string()
-}
+    fn parse(input: String) -> Self::Parsed {
+        let mut parser = MultiLineParser::new(&input);
+        let map: Map = (&mut parser).into();
+        (parser, map)
+    }
+
+    fn part1((parser, map): &Self::Parsed) -> Answer {
+        let mut parser = parser.clone();
+        let starts = map.get(&'0').unwrap();
 
-fn parse_input(input: &str) -> (MultiLineParser, Map) {
-    let mut parser = MultiLineParser::new(input);
-    let map: Map = (&mut parser).into();
-    (parser, map)
+        let sum = starts
+            .iter()
+            .map(|p| dfs(&mut parser, p, '9'))
+            .sum::<usize>();
+        sum.into()
+    }
+
+    fn part2((parser, map): &Self::Parsed) -> Answer {
+        let mut parser = parser.clone();
+        let starts = map.get(&'0').unwrap();
+
+        let sum = starts
+            .iter()
+            .map(|p| dfs_part2(&mut parser, p, '9'))
+            .sum::<usize>();
+        sum.into()
+    }
 }
 
 fn dfs(parser: &mut MultiLineParser, source: &Point, needle: char) -> usize {
@@ -67,6 +76,7 @@ fn walk(parser: &mut MultiLineParser, needle: char) -> Vec<Point> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{part1, part2};
     const INPUT: &str = "
 89010123
 78121874
@@ -79,13 +89,13 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let result = part1(INPUT.to_string());
+        let result = part1::<HoofIt>(INPUT.to_string());
         assert_eq!(result, "36");
     }
 
     #[test]
     fn test_part2() {
-        let result = part2(INPUT.to_string());
+        let result = part2::<HoofIt>(INPUT.to_string());
         assert_eq!(result, "81");
     }
 
@@ -93,7 +103,7 @@ mod tests {
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
         assert_eq!(
-            part1(include_str!("../../.data/y2024/d10.txt").to_string()),
+            part1::<HoofIt>(crate::solution::cached_input(2024, 10)),
             "820"
         );
     }
@@ -102,7 +112,7 @@ mod tests {
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
         assert_eq!(
-            part2(include_str!("../../.data/y2024/d10.txt").to_string()),
+            part2::<HoofIt>(crate::solution::cached_input(2024, 10)),
             "1786"
         );
     }
@@ -1,24 +1,25 @@
+use aoc_client::Answer;
 use cached::proc_macro::cached;
 
 use parser::Parser;
 
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let stones = Parser::new(&input).split_to_numbers(" ");
     let sum: usize = stones.iter().map(|s| blink_n(*s, 25)).sum();
-    sum.to_string()
+    sum.into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let stones = Parser::new(&input).split_to_numbers(" ");
     let sum: usize = stones.iter().map(|s| blink_n(*s, 75)).sum();
-    sum.to_string()
+    sum.into()
 }
 
 fn blink(num: i64) -> Vec<i64> {
     let str = num.to_string();
     match num {
         0 => vec![1],
-        _ if str.len() % 2 == 0 => {
+        _ if str.len().is_multiple_of(2) => {
             let left = str[0..str.len() / 2].parse().unwrap();
             let right = str[str.len() / 2..].parse().unwrap_or(0);
             vec![left, right]
@@ -66,17 +67,14 @@ mod tests {
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
-        assert_eq!(
-            part1(include_str!("../../.data/y2024/d11.txt").to_string()),
-            "217443"
-        );
+        assert_eq!(part1(crate::solution::cached_input(2024, 11)), "217443");
     }
 
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
         assert_eq!(
-            part2(include_str!("../../.data/y2024/d11.txt").to_string()),
+            part2(crate::solution::cached_input(2024, 11)),
             "257246536026785"
         );
     }
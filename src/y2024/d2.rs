@@ -1,19 +1,21 @@
-pub fn part1(input: String) -> String {
+use aoc_client::Answer;
+
+pub fn part1(input: String) -> Answer {
     let lists = parse_input(input);
     let safe_list_count = lists
         .iter()
         .filter(|list| is_increasing(list) || is_decreasing(list))
         .count();
-    safe_list_count.to_string()
+    safe_list_count.into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let lists = parse_input(input);
     let safe_list_count = lists
         .iter()
         .filter(|list| is_increasing_with_one_skipped(list) || is_decreasing_with_one_skipped(list))
         .count();
-    safe_list_count.to_string()
+    safe_list_count.into()
 }
 
 fn parse_input(input: String) -> Vec<Vec<i32>> {
@@ -95,18 +97,12 @@ mod tests {
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
-        assert_eq!(
-            part1(include_str!("../../.data/y2024/d2.txt").to_string()),
-            "326"
-        );
+        assert_eq!(part1(crate::solution::cached_input(2024, 2)), "326");
     }
 
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
-        assert_eq!(
-            part2(include_str!("../../.data/y2024/d2.txt").to_string()),
-            "381"
-        );
+        assert_eq!(part2(crate::solution::cached_input(2024, 2)), "381");
     }
 }
@@ -1,12 +1,13 @@
+use aoc_client::Answer;
 use parser::Parser;
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let nums = parse_input(&input);
-    nums.iter().map(|[a, b]| a * b).sum::<i64>().to_string()
+    nums.iter().map(|[a, b]| a * b).sum::<i64>().into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let nums = parse_input_2(&input);
-    nums.iter().map(|[a, b]| a * b).sum::<i64>().to_string()
+    nums.iter().map(|[a, b]| a * b).sum::<i64>().into()
 }
 
 fn parse_input(input: &str) -> Vec<[i64; 2]> {
@@ -49,18 +50,12 @@ mod tests {
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
-        assert_eq!(
-            part1(include_str!("../../.data/y2024/d3.txt").to_string()),
-            "184122457"
-        );
+        assert_eq!(part1(crate::solution::cached_input(2024, 3)), "184122457");
     }
 
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
-        assert_eq!(
-            part2(include_str!("../../.data/y2024/d3.txt").to_string()),
-            "107862689"
-        );
+        assert_eq!(part2(crate::solution::cached_input(2024, 3)), "107862689");
     }
 }
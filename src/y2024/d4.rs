@@ -1,5 +1,6 @@
+use aoc_client::Answer;
 use parser::MultiLineParser;
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let mut parser = parse_input(input);
     let mut sum = 0;
     while !parser.is_done() {
@@ -7,10 +8,10 @@ pub fn part1(input: String) -> String {
         sum += count;
         parser.pop();
     }
-    sum.to_string()
+    sum.into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let mut parser = parse_input(input);
     let mut sum = 0;
     while !parser.is_done() {
@@ -19,7 +20,7 @@ pub fn part2(input: String) -> String {
         }
         parser.pop();
     }
-    sum.to_string()
+    sum.into()
 }
 
 fn parse_input(input: String) -> MultiLineParser {
@@ -56,18 +57,12 @@ MXMXAXMASX
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
-        assert_eq!(
-            part1(include_str!("../../.data/y2024/d4.txt").to_string()),
-            "2401"
-        );
+        assert_eq!(part1(crate::solution::cached_input(2024, 4)), "2401");
     }
 
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
-        assert_eq!(
-            part2(include_str!("../../.data/y2024/d4.txt").to_string()),
-            "1822"
-        );
+        assert_eq!(part2(crate::solution::cached_input(2024, 4)), "1822");
     }
 }
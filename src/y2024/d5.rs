@@ -1,35 +1,42 @@
 use std::cmp::Ordering;
 
+use aoc_client::Answer;
 use parser::MultiLineParser;
 
-pub fn part1(input: String) -> String {
-    let (orders, pages) = parse_input(input);
-    let sum: i64 = pages
-        .iter()
-        .filter(|page| is_ordered(page, &orders))
-        .map(|page| page[page.len() / 2])
-        .sum();
+use crate::solution::Solution;
 
-    sum.to_string()
-}
+pub struct PrintQueue;
 
-pub fn part2(input: String) -> String {
-    let (orders, pages) = parse_input(input);
-    let sum: i64 = pages
-        .iter()
-        .filter(|page| !is_ordered(page, &orders))
-        .map(|page| sort_using_order(page.to_vec(), &orders))
-        .map(|page| page[page.len() / 2])
-        .sum();
+impl Solution for PrintQueue {
+    type Parsed = (Vec<Vec<i64>>, Vec<Vec<i64>>);
 
-    sum.to_string()
-}
+    fn parse(input: String) -> Self::Parsed {
+        let (orders_str, pages_str) = input.split_once("\n\n").unwrap();
+        let orders: Vec<Vec<i64>> = MultiLineParser::new(orders_str).split_to_numbers("|");
+        let pages: Vec<Vec<i64>> = MultiLineParser::new(pages_str).split_to_numbers(",");
+        (orders, pages)
+    }
+
+    fn part1((orders, pages): &Self::Parsed) -> Answer {
+        let sum: i64 = pages
+            .iter()
+            .filter(|page| is_ordered(page, orders))
+            .map(|page| page[page.len() / 2])
+            .sum();
 
-fn parse_input(input: String) -> (Vec<Vec<i64>>, Vec<Vec<i64>>) {
-    let (orders_str, pages_str) = input.split_once("\n\n").unwrap();
-    let orders: Vec<Vec<i64>> = MultiLineParser::new(orders_str).split_to_numbers("|");
-    let pages: Vec<Vec<i64>> = MultiLineParser::new(pages_str).split_to_numbers(",");
-    (orders, pages)
+        sum.into()
+    }
+
+    fn part2((orders, pages): &Self::Parsed) -> Answer {
+        let sum: i64 = pages
+            .iter()
+            .filter(|page| !is_ordered(page, orders))
+            .map(|page| sort_using_order(page.to_vec(), orders))
+            .map(|page| page[page.len() / 2])
+            .sum();
+
+        sum.into()
+    }
 }
 
 fn is_ordered(page: &[i64], orders: &[Vec<i64>]) -> bool {
@@ -54,6 +61,7 @@ fn sort_using_order(mut page: Vec<i64>, orders: &[Vec<i64>]) -> Vec<i64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{part1, part2};
     const INPUT: &str = "47|53
 97|13
 97|61
@@ -85,13 +93,13 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let result = part1(INPUT.to_string());
+        let result = part1::<PrintQueue>(INPUT.to_string());
         assert_eq!(result, "143");
     }
 
     #[test]
     fn test_part2() {
-        let result = part2(INPUT.to_string());
+        let result = part2::<PrintQueue>(INPUT.to_string());
         assert_eq!(result, "123");
     }
 
@@ -99,7 +107,7 @@ mod tests {
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
         assert_eq!(
-            part1(include_str!("../../.data/y2024/d5.txt").to_string()),
+            part1::<PrintQueue>(crate::solution::cached_input(2024, 5)),
             "4872"
         );
     }
@@ -108,7 +116,7 @@ mod tests {
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
         assert_eq!(
-            part2(include_str!("../../.data/y2024/d5.txt").to_string()),
+            part2::<PrintQueue>(crate::solution::cached_input(2024, 5)),
             "5564"
         );
     }
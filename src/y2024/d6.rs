@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use aoc_client::Answer;
 use parser::{Direction, MultiLineParser};
 
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let mut parser = parse_input(input);
     parser.advance_to("^");
     let mut cur = parser.point();
@@ -17,10 +18,10 @@ pub fn part1(input: String) -> String {
     let point = (parser.line(), parser.cursor());
     parser.fill(&'x', cur, point);
 
-    parser.count_chars(&'x').to_string()
+    parser.count_chars(&'x').into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let mut parser = parse_input(input);
     parser.advance_to("^");
     let start = parser.point();
@@ -58,7 +59,7 @@ pub fn part2(input: String) -> String {
         }
     }
 
-    points.len().to_string()
+    points.len().into()
 }
 
 fn does_obstacle_cause_cycle(parser: &mut MultiLineParser) -> bool {
@@ -114,18 +115,12 @@ mod tests {
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
-        assert_eq!(
-            part1(include_str!("../../.data/y2024/d6.txt").to_string()),
-            "4515"
-        );
+        assert_eq!(part1(crate::solution::cached_input(2024, 6)), "4515");
     }
 
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
-        assert_eq!(
-            part2(include_str!("../../.data/y2024/d6.txt").to_string()),
-            "1309"
-        );
+        assert_eq!(part2(crate::solution::cached_input(2024, 6)), "1309");
     }
 }
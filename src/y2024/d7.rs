@@ -1,42 +1,59 @@
+use aoc_client::Answer;
 use parser::MultiLineParser;
 
-pub fn part1(input: String) -> String {
-    let equations = parse_input(input);
-    equations
-        .iter()
-        .filter(|e| can_be_verified(e))
-        .map(|e| e[0])
-        .sum::<i64>()
-        .to_string()
-}
+use crate::solution::Solution;
 
-pub fn part2(input: String) -> String {
-    let equations = parse_input(input);
-    equations
-        .iter()
-        .filter(|e| can_be_verified(e) || can_be_verified_part2(e))
-        .map(|e| e[0])
-        .sum::<i64>()
-        .to_string()
-}
+pub struct BridgeRepair;
 
-fn parse_input(input: String) -> Vec<Vec<i64>> {
-    let mut parser = MultiLineParser::new(&input);
-    let mut equations: Vec<Vec<i64>> = parser
-        .match_number_up_to(':')
-        .iter()
-        .map(|num| vec![num.unwrap()])
-        .collect();
+impl Solution for BridgeRepair {
+    type Parsed = Vec<Vec<i64>>;
 
-    parser.advance_all_lines(1);
+    fn parse(input: String) -> Self::Parsed {
+        let mut parser = MultiLineParser::new(&input);
+        let mut equations: Vec<Vec<i64>> = parser
+            .match_number_up_to(':')
+            .iter()
+            .map(|num| vec![num.unwrap()])
+            .collect();
 
-    parser
-        .split_to_numbers(" ")
-        .iter()
-        .enumerate()
-        .for_each(|(i, nums)| nums.iter().for_each(|num| equations[i].push(*num)));
+        parser.advance_all_lines(1);
 
+        parser
+            .split_to_numbers(" ")
+            .iter()
+            .enumerate()
+            .for_each(|(i, nums)| nums.iter().for_each(|num| equations[i].push(*num)));
+
+        equations
+    }
+
+    fn part1(equations: &Self::Parsed) -> Answer {
+        equations
+            .iter()
+            .filter(|e| can_be_verified(e))
+            .map(|e| e[0])
+            .sum::<i64>()
+            .into()
+    }
+
+    fn part2(equations: &Self::Parsed) -> Answer {
+        equations
+            .iter()
+            .filter(|e| can_be_verified(e) || can_be_verified_part2(e))
+            .map(|e| e[0])
+            .sum::<i64>()
+            .into()
+    }
+}
+
+/// Same as `part2`, working back from the result so most operators are ruled out early
+pub fn part2_fast(equations: &<BridgeRepair as Solution>::Parsed) -> Answer {
     equations
+        .iter()
+        .filter(|e| can_be_reached(e[0], &e[1..]))
+        .map(|e| e[0])
+        .sum::<i64>()
+        .into()
 }
 
 fn can_be_verified(nums: &[i64]) -> bool {
@@ -73,9 +90,27 @@ fn can_be_verified_part2(nums: &[i64]) -> bool {
     }
 }
 
+/// Whether the numbers can produce the target, undoing the last operation first
+fn can_be_reached(target: i64, nums: &[i64]) -> bool {
+    let Some((&last, rest)) = nums.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return target == last;
+    }
+    let mut magnitude = 10;
+    while magnitude <= last {
+        magnitude *= 10;
+    }
+    (target > last && can_be_reached(target - last, rest))
+        || (last != 0 && target % last == 0 && can_be_reached(target / last, rest))
+        || (target > last && target % magnitude == last && can_be_reached(target / magnitude, rest))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{part1, part2};
     const INPUT: &str = "
 190: 10 19
 3267: 81 40 27
@@ -90,13 +125,19 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let result = part1(INPUT.to_string());
+        let result = part1::<BridgeRepair>(INPUT.to_string());
         assert_eq!(result, "3749");
     }
 
     #[test]
     fn test_part2() {
-        let result = part2(INPUT.to_string());
+        let result = part2::<BridgeRepair>(INPUT.to_string());
+        assert_eq!(result, "11387");
+    }
+
+    #[test]
+    fn test_part2_fast() {
+        let result = part2_fast(&BridgeRepair::parse(INPUT.to_string()));
         assert_eq!(result, "11387");
     }
 
@@ -104,7 +145,7 @@ mod tests {
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
         assert_eq!(
-            part1(include_str!("../../.data/y2024/d7.txt").to_string()),
+            part1::<BridgeRepair>(crate::solution::cached_input(2024, 7)),
             "66343330034722"
         );
     }
@@ -113,7 +154,7 @@ mod tests {
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
         assert_eq!(
-            part2(include_str!("../../.data/y2024/d7.txt").to_string()),
+            part2::<BridgeRepair>(crate::solution::cached_input(2024, 7)),
             "637696070419031"
         );
     }
@@ -1,19 +1,20 @@
+use aoc_client::Answer;
 use parser::{Map, MultiLineParser};
 
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let mut parser = parse_input(input);
     let mut map: Map = (&mut parser).into();
     map.remove(&'.');
     let mut antinodes = get_antinodes_from_map(map, parser);
-    antinodes.count_chars(&'#').to_string()
+    antinodes.count_chars(&'#').into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let mut parser = parse_input(input);
     let mut map: Map = (&mut parser).into();
     map.remove(&'.');
     let mut antinodes = get_antinodes_from_map_part2(map, parser);
-    antinodes.count_chars(&'#').to_string()
+    antinodes.count_chars(&'#').into()
 }
 
 fn parse_input(input: String) -> MultiLineParser {
@@ -108,18 +109,12 @@ mod tests {
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
-        assert_eq!(
-            part1(include_str!("../../.data/y2024/d8.txt").to_string()),
-            "220"
-        );
+        assert_eq!(part1(crate::solution::cached_input(2024, 8)), "220");
     }
 
     #[test]
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
-        assert_eq!(
-            part2(include_str!("../../.data/y2024/d8.txt").to_string()),
-            "813"
-        );
+        assert_eq!(part2(crate::solution::cached_input(2024, 8)), "813");
     }
 }
@@ -1,6 +1,7 @@
+use aoc_client::Answer;
 use parser::Parser;
 
-pub fn part1(input: String) -> String {
+pub fn part1(input: String) -> Answer {
     let mut disk = parse_input(input);
 
     let mut i = 0;
@@ -22,10 +23,10 @@ pub fn part1(input: String) -> String {
         }
     }
 
-    sum.to_string()
+    sum.into()
 }
 
-pub fn part2(input: String) -> String {
+pub fn part2(input: String) -> Answer {
     let mut disk = parse_input2(input);
 
     let mut j = disk.len();
@@ -61,7 +62,7 @@ pub fn part2(input: String) -> String {
         }
     }
 
-    sum.to_string()
+    sum.into()
 }
 
 fn parse_input(input: String) -> Vec<i64> {
@@ -152,7 +153,7 @@ mod tests {
     #[cfg(feature = "test_input")]
     fn test_part1_input() {
         assert_eq!(
-            part1(include_str!("../../.data/y2024/d9.txt").to_string()),
+            part1(crate::solution::cached_input(2024, 9)),
             "6386640365805"
         );
     }
@@ -161,7 +162,7 @@ mod tests {
     #[cfg(feature = "test_input")]
     fn test_part2_input() {
         assert_eq!(
-            part2(include_str!("../../.data/y2024/d9.txt").to_string()),
+            part2(crate::solution::cached_input(2024, 9)),
             "6423258376982"
         );
     }
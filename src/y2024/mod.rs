@@ -1,95 +1,121 @@
 //! Auto-generated file by build script, do not edit!
-pub mod d10;
-pub mod d11;
-pub mod d4;
 pub mod d1;
-pub mod d5;
 pub mod d2;
+pub mod d3;
+pub mod d4;
+pub mod d5;
 pub mod d6;
 pub mod d7;
-pub mod d3;
 pub mod d8;
 pub mod d9;
-/// Selects the function for the given day and part
-pub fn select_function(day: u32, part: u32) -> Result<fn(String) -> String, String> {
+pub mod d10;
+pub mod d11;
+/// Selects the solution for the given day
+pub fn select_day(day: u32) -> Result<Box<dyn crate::solution::DynSolution>, String> {
     match day {
-        10 => {
-            match part {
-                1 => Ok(d10::part1),
-                2 => Ok(d10::part2),
-                _ => Err("Invalid part!".into()),
-            }
-        }
-        11 => {
-            match part {
-                1 => Ok(d11::part1),
-                2 => Ok(d11::part2),
-                _ => Err("Invalid part!".into()),
-            }
-        }
-        4 => {
-            match part {
-                1 => Ok(d4::part1),
-                2 => Ok(d4::part2),
-                _ => Err("Invalid part!".into()),
-            }
-        }
         1 => {
-            match part {
-                1 => Ok(d1::part1),
-                2 => Ok(d1::part2),
-                _ => Err("Invalid part!".into()),
-            }
-        }
-        5 => {
-            match part {
-                1 => Ok(d5::part1),
-                2 => Ok(d5::part2),
-                _ => Err("Invalid part!".into()),
-            }
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d1::part1,
+                    part2: d1::part2,
+                }),
+            )
         }
         2 => {
-            match part {
-                1 => Ok(d2::part1),
-                2 => Ok(d2::part2),
-                _ => Err("Invalid part!".into()),
-            }
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d2::part1,
+                    part2: d2::part2,
+                }),
+            )
         }
-        6 => {
-            match part {
-                1 => Ok(d6::part1),
-                2 => Ok(d6::part2),
-                _ => Err("Invalid part!".into()),
-            }
+        3 => {
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d3::part1,
+                    part2: d3::part2,
+                }),
+            )
         }
-        7 => {
-            match part {
-                1 => Ok(d7::part1),
-                2 => Ok(d7::part2),
-                _ => Err("Invalid part!".into()),
-            }
+        4 => {
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d4::part1,
+                    part2: d4::part2,
+                }),
+            )
         }
-        3 => {
-            match part {
-                1 => Ok(d3::part1),
-                2 => Ok(d3::part2),
-                _ => Err("Invalid part!".into()),
-            }
+        5 => Ok(Box::new(crate::solution::Typed::<d5::PrintQueue>::new())),
+        6 => {
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d6::part1,
+                    part2: d6::part2,
+                }),
+            )
         }
+        7 => Ok(Box::new(crate::solution::Typed::<d7::BridgeRepair>::new())),
         8 => {
-            match part {
-                1 => Ok(d8::part1),
-                2 => Ok(d8::part2),
-                _ => Err("Invalid part!".into()),
-            }
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d8::part1,
+                    part2: d8::part2,
+                }),
+            )
         }
         9 => {
-            match part {
-                1 => Ok(d9::part1),
-                2 => Ok(d9::part2),
-                _ => Err("Invalid part!".into()),
-            }
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d9::part1,
+                    part2: d9::part2,
+                }),
+            )
+        }
+        10 => Ok(Box::new(crate::solution::Typed::<d10::HoofIt>::new())),
+        11 => {
+            Ok(
+                Box::new(crate::solution::Functions {
+                    part1: d11::part1,
+                    part2: d11::part2,
+                }),
+            )
         }
         _ => Err("Invalid day!".into()),
     }
 }
+/// Returns the names of the variants of the given day
+pub fn variants(day: u32) -> &'static [&'static str] {
+    match day {
+        1 => &[],
+        2 => &[],
+        3 => &[],
+        4 => &[],
+        5 => &[],
+        6 => &[],
+        7 => &["part2_fast"],
+        8 => &[],
+        9 => &[],
+        10 => &[],
+        11 => &[],
+        _ => &[],
+    }
+}
+/// Selects the given day with one part replaced by the named variant
+#[allow(clippy::match_single_binding)]
+pub fn select_variant(
+    day: u32,
+    name: &str,
+) -> Result<Box<dyn crate::solution::DynSolution>, String> {
+    match (day, name) {
+        (7, "part2_fast") => {
+            Ok(
+                Box::new(
+                    crate::solution::Variant::<
+                        d7::BridgeRepair,
+                    >::new(2u8, d7::part2_fast),
+                ),
+            )
+        }
+        _ => Err("Invalid variant!".into()),
+    }
+}